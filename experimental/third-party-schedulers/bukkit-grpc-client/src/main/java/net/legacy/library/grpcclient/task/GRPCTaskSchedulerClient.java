@@ -334,7 +334,7 @@ public class GRPCTaskSchedulerClient {
      * Polls the gRPC {@code GetResult} endpoint until the task result is ready or an error occurs.
      *
      * @param taskId the identifier of the task whose result is needed
-     * @return the result string once available
+     * @return the result string once available, or {@code null} if the task completed without persisting its result
      * @throws TaskSchedulerException if polling fails (e.g., timeout, definitive server error)
      */
     private String getResultWhenReady(String taskId) throws TaskSchedulerException {
@@ -373,6 +373,9 @@ public class GRPCTaskSchedulerClient {
                     case FAILED:
                         Log.error("Task %s failed on server according to GetResult: %s", taskId, response.getResult());
                         throw new TaskSchedulerException("Task failed on server (reported by GetResult): " + response.getResult());
+                    case NOT_PERSISTED:
+                        // The task succeeded, its result was just not kept
+                        return null;
                     case UNRECOGNIZED:
                     default:
                         Log.error("GetResult received unrecognized status for task %s: %s", taskId, status);
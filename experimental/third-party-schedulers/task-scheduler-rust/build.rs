@@ -1,4 +1,10 @@
+use std::path::PathBuf;
+
 fn main() {
-    tonic_build::compile_protos("../proto/task_scheduler.proto")
+    let out_dir = PathBuf::from(std::env::var("OUT_DIR").expect("OUT_DIR is not set"));
+
+    tonic_build::configure()
+        .file_descriptor_set_path(out_dir.join("task_scheduler_descriptor.bin"))
+        .compile_protos(&["../proto/task_scheduler.proto"], &["../proto"])
         .unwrap_or_else(|e| panic!("Failed to compile protos: {}", e));
 }
@@ -0,0 +1,22 @@
+/// Stub task that must never be registered
+#[no_mangle]
+pub unsafe fn noop() {}
+
+/// Report an ABI version no host supports
+#[no_mangle]
+pub unsafe fn plugin_abi_version() -> u32 {
+    0
+}
+
+// Task entry point information
+type TaskEntryInfo = (&'static str, bool, usize);
+
+/// Initialize plugin, return task list
+#[no_mangle]
+pub unsafe fn init_plugin() -> &'static [TaskEntryInfo] {
+    static mut TASKS: [TaskEntryInfo; 1] = [("noop", false, 0)];
+
+    TASKS[0].2 = noop as usize;
+
+    &TASKS
+}
@@ -30,7 +30,14 @@ pub unsafe fn delayed_echo(args: Vec<ArgValue>) -> Pin<Box<dyn Future<Output = S
             .collect::<Vec<_>>()
             .join(" ");
 
-        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        // The plugin links its own copy of tokio, which cannot reach the host runtime's timer,
+        // so the delay runs on a plain thread and only the channel is awaited
+        let (sender, receiver) = tokio::sync::oneshot::channel();
+        std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(500));
+            let _ = sender.send(());
+        });
+        let _ = receiver.await;
 
         // Return result directly
         format!(
@@ -52,6 +59,12 @@ pub unsafe fn get_delayed_echo_ptr() -> usize {
     delayed_echo as usize
 }
 
+// Report the plugin ABI version this plugin was built against
+#[no_mangle]
+pub unsafe fn plugin_abi_version() -> u32 {
+    task_scheduler::tasks::dynamic::PLUGIN_ABI_VERSION
+}
+
 // Task entry point information
 type TaskEntryInfo = (&'static str, bool, usize);
 
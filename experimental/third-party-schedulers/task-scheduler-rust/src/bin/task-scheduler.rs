@@ -2,13 +2,22 @@ use anyhow::{Context, Result};
 use clap::{self, CommandFactory, Parser};
 use rustyline::DefaultEditor;
 use std::path::PathBuf;
+use std::time::Duration;
 use task_scheduler::logger;
-use task_scheduler::server::service::TaskSchedulerService;
-use task_scheduler::tasks::dynamic::init_dynamic_loader;
+use task_scheduler::server::admin::{AdminAuth, AdminService};
+use task_scheduler::server::logging::{RpcLogLevel, RpcLoggingLayer};
+use task_scheduler::server::service::{TaskSchedulerService, DEFAULT_DEADLINE_MARGIN_PERCENT};
+use task_scheduler::tasks::circuit_breaker::CircuitBreakerConfig;
+use task_scheduler::tasks::dynamic::{
+    init_dynamic_loader, init_dynamic_loader_with_trusted_keys, load_trusted_keys,
+};
+use task_scheduler::tasks::retry_budget::RetryBudgetConfig;
+use task_scheduler::tasks::taskscheduler::task_scheduler_admin_server::TaskSchedulerAdminServer;
 use task_scheduler::tasks::taskscheduler::task_scheduler_server::TaskSchedulerServer;
+use task_scheduler::tasks::taskscheduler::FILE_DESCRIPTOR_SET;
 use task_scheduler::tasks::{
     list_all_tasks, list_loaded_plugins, load_plugin, log_pending_registrations,
-    reload_all_plugins, unload_plugin, DYNAMIC_LOADER,
+    reload_all_plugins, unload_plugin, DYNAMIC_LOADER, REGISTRY,
 };
 use tokio::fs;
 use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
@@ -44,6 +53,86 @@ struct Args {
     /// Start CLI mode for interactive commands
     #[arg(short, long)]
     cli: bool,
+
+    /// Maximum encoded size in bytes of a task's arguments (0 disables the limit)
+    #[arg(long, default_value_t = 1024 * 1024)]
+    max_args_bytes: usize,
+
+    /// Refuse to load plugins without a valid signature from a trusted key
+    #[arg(long, requires = "trusted_plugin_keys")]
+    require_signed_plugins: bool,
+
+    /// File with hex-encoded ed25519 public keys (one per line) trusted to sign plugins
+    #[arg(long)]
+    trusted_plugin_keys: Option<PathBuf>,
+
+    /// Directory verified plugins are copied to before loading (defaults to the library directory)
+    #[arg(long)]
+    verified_plugin_copy_dir: Option<PathBuf>,
+
+    /// Consecutive failures before a method's circuit breaker opens (0, the default, disables it)
+    #[arg(long, default_value_t = 0)]
+    circuit_breaker_threshold: u32,
+
+    /// Seconds an open circuit breaker rejects tasks before letting a probe through
+    #[arg(long, default_value_t = 30)]
+    circuit_breaker_open_secs: u64,
+
+    /// Retries that can be spent in a burst across all tasks (0 disables the budget)
+    #[arg(long, default_value_t = 100)]
+    retry_budget: u32,
+
+    /// Retries added back to the budget per second
+    #[arg(long, default_value_t = 10.0, value_parser = parse_refill_rate)]
+    retry_budget_refill_per_sec: f64,
+
+    /// Which RPCs to log with their method, duration and status
+    #[arg(long, value_enum, default_value_t = RpcLogLevel::Errors)]
+    rpc_log: RpcLogLevel,
+
+    /// Sync task threads alive at once, including ones abandoned on timeout (0 disables the cap)
+    #[arg(long, default_value_t = 256)]
+    max_sync_threads: usize,
+
+    /// Percentage of a task's timeout after which a warning is logged (0 disables it)
+    #[arg(long, default_value_t = 80, value_parser = clap::value_parser!(u8).range(0..100))]
+    soft_timeout_percent: u8,
+
+    /// Percentage of a client deadline reserved for the response to reach the client (at least 50ms)
+    #[arg(long, default_value_t = DEFAULT_DEADLINE_MARGIN_PERCENT, value_parser = clap::value_parser!(u8).range(0..100))]
+    deadline_margin_percent: u8,
+
+    /// Seconds in-flight requests may take to finish after a shutdown signal before exiting anyway
+    #[arg(long, default_value_t = 30)]
+    shutdown_grace_secs: u64,
+
+    /// File holding the bearer token required by the admin service, which is only served when set
+    #[arg(long)]
+    admin_token_file: Option<PathBuf>,
+
+    /// Deprecated method name to keep accepting, as `old=new` (repeatable)
+    #[arg(long, value_parser = parse_method_alias)]
+    method_alias: Vec<(String, String)>,
+}
+
+fn parse_method_alias(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some((alias, method)) if !alias.is_empty() && !method.is_empty() => {
+            Ok((alias.to_string(), method.to_string()))
+        }
+        _ => Err(format!("expected `old=new`, got `{}`", value)),
+    }
+}
+
+fn parse_refill_rate(value: &str) -> Result<f64, String> {
+    match value.parse::<f64>() {
+        Ok(rate) if rate.is_finite() && rate >= 0.0 => Ok(rate),
+        Ok(_) => Err(format!(
+            "expected a finite, non-negative number, got `{}`",
+            value
+        )),
+        Err(e) => Err(format!("`{}` is not a number: {}", value, e)),
+    }
 }
 
 async fn load_identity(cert_path: &PathBuf, key_path: &PathBuf) -> Result<Identity> {
@@ -56,6 +145,19 @@ async fn load_identity(cert_path: &PathBuf, key_path: &PathBuf) -> Result<Identi
     Ok(Identity::from_pem(cert_pem, key_pem))
 }
 
+async fn load_admin_token(path: &PathBuf) -> Result<String> {
+    let token = fs::read_to_string(path)
+        .await
+        .with_context(|| format!("Failed to read admin token file: {}", path.display()))?;
+    let token = token.trim();
+    anyhow::ensure!(
+        !token.is_empty(),
+        "Admin token file is empty: {}",
+        path.display()
+    );
+    Ok(token.to_string())
+}
+
 async fn load_ca_cert(ca_path: &PathBuf) -> Result<Certificate> {
     let ca_pem = fs::read(ca_path)
         .await
@@ -63,6 +165,34 @@ async fn load_ca_cert(ca_path: &PathBuf) -> Result<Certificate> {
     Ok(Certificate::from_pem(ca_pem))
 }
 
+/// Resolves on ctrl-c or, on Unix, on SIGTERM as sent by Kubernetes when terminating a pod.
+async fn shutdown_signal() {
+    let ctrl_c = async {
+        let _ = tokio::signal::ctrl_c().await;
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(e) => {
+                warn_log!("Failed to listen for SIGTERM: {}", e);
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
 fn run_cli_mode() -> Result<()> {
     let rt = tokio::runtime::Builder::new_current_thread()
         .enable_all()
@@ -251,7 +381,41 @@ async fn main() -> Result<()> {
 
     let args = Args::parse();
 
-    init_dynamic_loader(args.library_dir.clone());
+    REGISTRY.circuit_breaker().configure(CircuitBreakerConfig {
+        failure_threshold: args.circuit_breaker_threshold,
+        open_duration: Duration::from_secs(args.circuit_breaker_open_secs),
+    });
+    REGISTRY.retry_budget().configure(RetryBudgetConfig {
+        capacity: args.retry_budget,
+        refill_per_sec: args.retry_budget_refill_per_sec,
+    });
+    REGISTRY.set_max_args_size(args.max_args_bytes);
+    REGISTRY.set_max_sync_threads(args.max_sync_threads);
+    REGISTRY.set_soft_timeout_percent(args.soft_timeout_percent);
+    for (alias, method) in &args.method_alias {
+        REGISTRY.register_alias(alias, method);
+    }
+
+    match (&args.trusted_plugin_keys, args.require_signed_plugins) {
+        (Some(keys_path), true) => {
+            let trusted_keys =
+                load_trusted_keys(keys_path).context("Failed to load trusted plugin keys")?;
+            info_log!(
+                "Plugin signature verification enabled with {} trusted keys",
+                trusted_keys.len()
+            );
+            init_dynamic_loader_with_trusted_keys(
+                args.library_dir.clone(),
+                trusted_keys,
+                args.verified_plugin_copy_dir.clone(),
+            );
+        }
+        (Some(_), false) => {
+            warn_log!("Ignoring --trusted-plugin-keys without --require-signed-plugins");
+            init_dynamic_loader(args.library_dir.clone());
+        }
+        (None, _) => init_dynamic_loader(args.library_dir.clone()),
+    }
 
     {
         let loader = DYNAMIC_LOADER.lock();
@@ -282,8 +446,6 @@ async fn main() -> Result<()> {
         .addr
         .parse()
         .with_context(|| format!("Failed to parse address: {}", args.addr))?;
-    let service = TaskSchedulerService::default();
-
     let mut server_builder = Server::builder();
     let mut tls_enabled = false;
 
@@ -320,11 +482,62 @@ async fn main() -> Result<()> {
         if tls_enabled { " (TLS)" } else { "" }
     );
 
-    server_builder
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    health_reporter
+        .set_serving::<TaskSchedulerServer<TaskSchedulerService>>()
+        .await;
+    let service = TaskSchedulerService::with_health_reporter(health_reporter)
+        .with_deadline_margin_percent(args.deadline_margin_percent);
+    let serving_state = service.serving_state();
+    let admin_service = match &args.admin_token_file {
+        Some(path) => {
+            let token = load_admin_token(path).await?;
+            info_log!("Admin service enabled");
+            Some(TaskSchedulerAdminServer::with_interceptor(
+                AdminService::new(serving_state.clone()),
+                AdminAuth::new(&token),
+            ))
+        }
+        None => None,
+    };
+
+    let reflection_service = tonic_reflection::server::Builder::configure()
+        .register_encoded_file_descriptor_set(FILE_DESCRIPTOR_SET)
+        .build_v1()
+        .context("Failed to build reflection service")?;
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+    let server = server_builder
+        .layer(RpcLoggingLayer::new(args.rpc_log))
+        .add_service(health_service)
+        .add_service(reflection_service)
         .add_service(TaskSchedulerServer::new(service))
-        .serve(addr)
-        .await
-        .context("Failed to start Tonic server")?;
+        .add_optional_service(admin_service)
+        .serve_with_shutdown(addr, async move {
+            shutdown_signal().await;
+            info_log!("Shutdown signal received, draining in-flight requests");
+            serving_state.set_accepting(false).await;
+            let _ = shutdown_tx.send(());
+        });
+
+    // Draining waits for every in-flight request, so a hung task would otherwise block shutdown
+    let grace = Duration::from_secs(args.shutdown_grace_secs);
+    tokio::select! {
+        result = server => result.context("Failed to start Tonic server")?,
+        _ = async {
+            if shutdown_rx.await.is_ok() {
+                tokio::time::sleep(grace).await;
+            } else {
+                std::future::pending::<()>().await;
+            }
+        } => {
+            warn_log!(
+                "Shutdown grace period of {}s expired, exiting with requests still in flight",
+                args.shutdown_grace_secs
+            );
+            anyhow::bail!("Requests did not finish within the shutdown grace period");
+        }
+    }
 
     Ok(())
 }
@@ -13,6 +13,26 @@ pub enum TaskError {
 
     #[error("Task execution failed: {0}")]
     ExecutionError(String),
+
+    #[error("Task timed out: {0}")]
+    Timeout(String),
+
+    #[error("Plugin in use: {0}")]
+    PluginInUse(String),
+
+    #[error("Circuit open: {0}")]
+    CircuitOpen(String),
+
+    #[error("Resource exhausted: {0}")]
+    ResourceExhausted(String),
+}
+
+impl TaskError {
+    /// Returns whether retrying the task could succeed. Only execution failures and timeouts are
+    /// transient, the other errors would fail again with the same request.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, TaskError::ExecutionError(_) | TaskError::Timeout(_))
+    }
 }
 
 pub type Result<T> = std::result::Result<T, TaskError>;
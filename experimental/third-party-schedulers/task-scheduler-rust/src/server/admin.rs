@@ -0,0 +1,98 @@
+use crate::server::service::ServingState;
+use crate::tasks::circuit_breaker::CircuitState;
+use crate::tasks::taskscheduler::stats_response;
+use crate::tasks::taskscheduler::task_scheduler_admin_server::TaskSchedulerAdmin;
+use crate::tasks::taskscheduler::{
+    SetAcceptingRequest, SetAcceptingResponse, StatsRequest, StatsResponse,
+};
+use crate::tasks::REGISTRY;
+use crate::warn_log;
+use std::sync::Arc;
+use tonic::service::Interceptor;
+use tonic::{Request, Response, Status};
+
+/// Operator calls that change how the server behaves, served behind [`AdminAuth`].
+#[derive(Debug)]
+pub struct AdminService {
+    state: ServingState,
+}
+
+impl AdminService {
+    pub fn new(state: ServingState) -> Self {
+        Self { state }
+    }
+}
+
+#[tonic::async_trait]
+impl TaskSchedulerAdmin for AdminService {
+    async fn set_accepting(
+        &self,
+        request: Request<SetAcceptingRequest>,
+    ) -> Result<Response<SetAcceptingResponse>, Status> {
+        let accepting = request.into_inner().accepting;
+        self.state.set_accepting(accepting).await;
+        Ok(Response::new(SetAcceptingResponse { accepting }))
+    }
+
+    async fn get_stats(
+        &self,
+        _request: Request<StatsRequest>,
+    ) -> Result<Response<StatsResponse>, Status> {
+        Ok(Response::new(StatsResponse {
+            alias_uses: REGISTRY.alias_use_counts(),
+            abandoned_sync_threads: REGISTRY.abandoned_thread_count() as u64,
+            live_sync_threads: REGISTRY.sync_thread_count() as u64,
+            circuit_states: REGISTRY
+                .circuit_breaker()
+                .states()
+                .into_iter()
+                .map(|(method, state)| {
+                    let state = match state {
+                        CircuitState::Closed => stats_response::CircuitState::Closed,
+                        CircuitState::Open => stats_response::CircuitState::Open,
+                        CircuitState::HalfOpen => stats_response::CircuitState::HalfOpen,
+                    };
+                    (method, state as i32)
+                })
+                .collect(),
+            retry_budget_remaining: REGISTRY.retry_budget().remaining(),
+            timeout_warnings: REGISTRY.timeout_warning_count() as u64,
+        }))
+    }
+}
+
+/// Rejects admin calls that do not carry `authorization: Bearer <token>` with the configured token.
+#[derive(Clone)]
+pub struct AdminAuth {
+    expected: Arc<[u8]>,
+}
+
+impl AdminAuth {
+    pub fn new(token: &str) -> Self {
+        Self {
+            expected: format!("Bearer {}", token).into_bytes().into(),
+        }
+    }
+}
+
+impl Interceptor for AdminAuth {
+    fn call(&mut self, request: Request<()>) -> Result<Request<()>, Status> {
+        let provided = request
+            .metadata()
+            .get("authorization")
+            .map(|value| value.as_bytes())
+            .unwrap_or_default();
+
+        if constant_time_eq(provided, &self.expected) {
+            Ok(request)
+        } else {
+            warn_log!("Rejected an admin call without a valid token");
+            Err(Status::unauthenticated("Missing or invalid admin token"))
+        }
+    }
+}
+
+/// Compares without exiting early, so the time taken does not reveal how much of the token matched.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |diff, (x, y)| diff | (x ^ y)) == 0
+}
@@ -0,0 +1,171 @@
+use crate::{error_log, info_log, warn_log};
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+use tonic::codegen::http::{HeaderValue, Request, Response};
+use tonic::codegen::{BoxFuture, Context, Poll, Service};
+use tonic::{Code, Status};
+use tower::Layer;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum RpcLogLevel {
+    /// Log nothing
+    Off,
+    /// Log only calls that did not complete with `OK`
+    Errors,
+    /// Log every call
+    All,
+}
+
+/// Header carrying the id that ties the log lines of one RPC together. A usable id sent by the
+/// client is kept, otherwise one is generated, and either way it is echoed on the response.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+
+/// Longest client-provided request id that is kept rather than replaced.
+const MAX_REQUEST_ID_LEN: usize = 64;
+
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Id of the RPC being served, attached to the request extensions by [`RpcLoggingLayer`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestId(String);
+
+impl RequestId {
+    /// The id attached to a request, `-` when it did not pass through [`RpcLoggingLayer`].
+    pub fn of<T>(request: &tonic::Request<T>) -> Self {
+        request
+            .extensions()
+            .get::<Self>()
+            .cloned()
+            .unwrap_or_else(|| Self("-".to_string()))
+    }
+
+    /// Keeps the client's id when it is short and only uses characters that are safe to log.
+    fn from_header<B>(request: &Request<B>) -> Self {
+        request
+            .headers()
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .filter(|id| {
+                !id.is_empty()
+                    && id.len() <= MAX_REQUEST_ID_LEN
+                    && id
+                        .chars()
+                        .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+            })
+            .map(|id| Self(id.to_string()))
+            .unwrap_or_else(|| {
+                Self(format!(
+                    "{:016x}",
+                    NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed)
+                ))
+            })
+    }
+}
+
+impl fmt::Display for RequestId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Logs every RPC with its method, duration and resulting gRPC status. Only the request path
+/// is logged, never the payload or metadata, so arguments and credentials stay out of the logs.
+#[derive(Debug, Clone, Copy)]
+pub struct RpcLoggingLayer {
+    level: RpcLogLevel,
+}
+
+impl RpcLoggingLayer {
+    pub fn new(level: RpcLogLevel) -> Self {
+        Self { level }
+    }
+}
+
+impl<S> Layer<S> for RpcLoggingLayer {
+    type Service = RpcLogging<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RpcLogging {
+            inner,
+            level: self.level,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcLogging<S> {
+    inner: S,
+    level: RpcLogLevel,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for RpcLogging<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    S::Error: std::fmt::Display,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<ReqBody>) -> Self::Future {
+        let level = self.level;
+        let method = request.uri().path().to_string();
+        let request_id = RequestId::from_header(&request);
+        request.extensions_mut().insert(request_id.clone());
+        let start = Instant::now();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let mut result = future.await;
+            let duration = start.elapsed().as_millis();
+
+            match &mut result {
+                Ok(response) => {
+                    if let Ok(value) = HeaderValue::from_str(&request_id.0) {
+                        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+                    }
+
+                    // Failed unary calls are trailers-only, so their status is in the headers
+                    let code = Status::from_header_map(response.headers())
+                        .map_or(Code::Ok, |status| status.code());
+                    if code != Code::Ok && level != RpcLogLevel::Off {
+                        warn_log!(
+                            "RPC {} returned {:?} in {}ms (request {})",
+                            method,
+                            code,
+                            duration,
+                            request_id
+                        );
+                    } else if level == RpcLogLevel::All {
+                        info_log!(
+                            "RPC {} returned {:?} in {}ms (request {})",
+                            method,
+                            code,
+                            duration,
+                            request_id
+                        );
+                    }
+                }
+                Err(err) => {
+                    if level != RpcLogLevel::Off {
+                        error_log!(
+                            "RPC {} failed in {}ms (request {}): {}",
+                            method,
+                            duration,
+                            request_id,
+                            err
+                        );
+                    }
+                }
+            }
+
+            result
+        })
+    }
+}
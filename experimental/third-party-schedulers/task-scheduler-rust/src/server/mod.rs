@@ -1,2 +1,4 @@
+pub mod admin;
+pub mod logging;
 pub mod service;
 pub use service::TaskSchedulerService;
@@ -1,16 +1,138 @@
 use crate::error::TaskError;
 use crate::error_log;
 use crate::info_log;
-use crate::tasks::taskscheduler::task_scheduler_server::TaskScheduler;
+use crate::server::logging::RequestId;
+use crate::tasks::taskscheduler::task_scheduler_server::{TaskScheduler, TaskSchedulerServer};
 use crate::tasks::taskscheduler::{
-    task_response, ResultRequest, ResultResponse, TaskRequest, TaskResponse,
+    task_response, ResultRequest, ResultResponse, TaskRequest, TaskResponse, ValidationResponse,
 };
 use crate::tasks::REGISTRY;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tonic::metadata::MetadataMap;
+use tonic::server::NamedService;
 use tonic::{Request, Response, Status};
+use tonic_health::server::HealthReporter;
+use tonic_health::ServingStatus;
 
-#[derive(Debug, Default)]
-pub struct TaskSchedulerService {}
+/// Share of a client deadline reserved by default for the response to reach the client. It also
+/// makes the task fail with `DEADLINE_EXCEEDED` before tonic cancels the call on the same deadline.
+pub const DEFAULT_DEADLINE_MARGIN_PERCENT: u8 = 10;
+
+/// Smallest margin reserved, so short deadlines still leave room for the response under load.
+const MIN_DEADLINE_MARGIN: Duration = Duration::from_millis(50);
+
+/// Parses the `grpc-timeout` header a client sends when it sets a deadline and subtracts the
+/// margin. tonic applies the same header in its own timeout layer but does not expose the value.
+fn client_deadline(metadata: &MetadataMap, margin_percent: u8) -> Option<Duration> {
+    let header = metadata.get("grpc-timeout")?.to_str().ok()?;
+    if header.len() < 2 || header.len() > 9 {
+        return None;
+    }
+
+    let (value, unit) = header.split_at(header.len() - 1);
+    let value: u64 = value.parse().ok()?;
+    let timeout = match unit {
+        "H" => Duration::from_secs(value * 60 * 60),
+        "M" => Duration::from_secs(value * 60),
+        "S" => Duration::from_secs(value),
+        "m" => Duration::from_millis(value),
+        "u" => Duration::from_micros(value),
+        "n" => Duration::from_nanos(value),
+        _ => return None,
+    };
+    let margin = (timeout / 100 * u32::from(margin_percent)).max(MIN_DEADLINE_MARGIN);
+    Some(timeout.saturating_sub(margin))
+}
+
+/// Whether the server accepts new tasks, shared between the task service and the admin service.
+#[derive(Debug, Clone)]
+pub struct ServingState {
+    accepting: Arc<AtomicBool>,
+    health_reporter: Option<HealthReporter>,
+}
+
+impl Default for ServingState {
+    fn default() -> Self {
+        Self {
+            accepting: Arc::new(AtomicBool::new(true)),
+            health_reporter: None,
+        }
+    }
+}
+
+impl ServingState {
+    pub fn is_accepting(&self) -> bool {
+        self.accepting.load(Ordering::SeqCst)
+    }
+
+    /// Stops or resumes accepting new tasks, flipping the health status to match.
+    pub async fn set_accepting(&self, accepting: bool) {
+        self.accepting.store(accepting, Ordering::SeqCst);
+
+        if let Some(health_reporter) = &self.health_reporter {
+            let status = if accepting {
+                ServingStatus::Serving
+            } else {
+                ServingStatus::NotServing
+            };
+            health_reporter
+                .set_service_status(TaskSchedulerServer::<TaskSchedulerService>::NAME, status)
+                .await;
+            // Probes such as grpc-health-probe check the overall "" service by default
+            health_reporter.set_service_status("", status).await;
+        }
+
+        info_log!(
+            "Server is {} new tasks",
+            if accepting {
+                "accepting"
+            } else {
+                "draining, rejecting"
+            }
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct TaskSchedulerService {
+    state: ServingState,
+    deadline_margin_percent: u8,
+}
+
+impl Default for TaskSchedulerService {
+    fn default() -> Self {
+        Self {
+            state: ServingState::default(),
+            deadline_margin_percent: DEFAULT_DEADLINE_MARGIN_PERCENT,
+        }
+    }
+}
+
+impl TaskSchedulerService {
+    /// Creates a service whose health status follows whether it accepts new tasks.
+    pub fn with_health_reporter(health_reporter: HealthReporter) -> Self {
+        Self {
+            state: ServingState {
+                health_reporter: Some(health_reporter),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    /// Sets the share of each client deadline reserved for the response to reach the client.
+    pub fn with_deadline_margin_percent(mut self, percent: u8) -> Self {
+        self.deadline_margin_percent = percent;
+        self
+    }
+
+    /// Handle to stop or resume accepting new tasks on this service.
+    pub fn serving_state(&self) -> ServingState {
+        self.state.clone()
+    }
+}
 
 #[tonic::async_trait]
 impl TaskScheduler for TaskSchedulerService {
@@ -18,41 +140,43 @@ impl TaskScheduler for TaskSchedulerService {
         &self,
         request: Request<TaskRequest>,
     ) -> Result<Response<TaskResponse>, Status> {
+        if !self.state.is_accepting() {
+            return Err(Status::unavailable(
+                "Server is draining and not accepting new tasks, retry on another instance",
+            ));
+        }
+
+        let request_id = RequestId::of(&request);
+        let deadline = client_deadline(request.metadata(), self.deadline_margin_percent);
         let task = request.into_inner();
         let task_id = task.task_id.clone();
-        let method = task.method.clone();
-        let is_async = task.is_async;
-
-        info_log!(
-            "Received task: {} with method: {}, async: {}",
-            task_id,
-            method,
-            is_async
-        );
 
         let start = Instant::now();
-        let execution_result = REGISTRY.execute_task(&task).await;
+        let execution_result = match deadline {
+            Some(deadline) => tokio::time::timeout(deadline, REGISTRY.execute_task(&task))
+                .await
+                .unwrap_or_else(|_| {
+                    Err(TaskError::Timeout(format!(
+                        "Task '{}' exceeded the client deadline",
+                        task_id
+                    )))
+                }),
+            None => REGISTRY.execute_task(&task).await,
+        };
         let duration = start.elapsed().as_millis();
 
         match execution_result {
-            Ok(value) => {
-                info_log!(
-                    "Completed task: {} successfully (took {}ms). Result: {}",
-                    task_id,
-                    duration,
-                    value
-                );
-                Ok(Response::new(TaskResponse {
-                    task_id,
-                    status: task_response::Status::Success as i32,
-                    result: value,
-                }))
-            }
+            Ok(value) => Ok(Response::new(TaskResponse {
+                task_id,
+                status: task_response::Status::Success as i32,
+                result: value,
+            })),
             Err(err) => {
                 error_log!(
-                    "Failed task: {} (took {}ms). Error: {}",
+                    "Failed task: {} (took {}ms, request {}). Error: {}",
                     task_id,
                     duration,
+                    request_id,
                     err
                 );
                 let status = match err {
@@ -68,6 +192,18 @@ impl TaskScheduler for TaskSchedulerService {
                     TaskError::ExecutionError(e) => {
                         Status::internal(format!("Task execution failed: {}", e))
                     }
+                    TaskError::Timeout(t) => {
+                        Status::deadline_exceeded(format!("Task timed out: {}", t))
+                    }
+                    TaskError::PluginInUse(p) => {
+                        Status::failed_precondition(format!("Plugin in use: {}", p))
+                    }
+                    TaskError::CircuitOpen(c) => {
+                        Status::unavailable(format!("Circuit open: {}", c))
+                    }
+                    TaskError::ResourceExhausted(r) => {
+                        Status::resource_exhausted(format!("Resource exhausted: {}", r))
+                    }
                 };
                 Err(status)
             }
@@ -91,4 +227,21 @@ impl TaskScheduler for TaskSchedulerService {
             }))
         }
     }
+
+    async fn validate_task(
+        &self,
+        request: Request<TaskRequest>,
+    ) -> Result<Response<ValidationResponse>, Status> {
+        let task = request.into_inner();
+        let problems: Vec<String> = REGISTRY
+            .validate_task(&task)
+            .iter()
+            .map(ToString::to_string)
+            .collect();
+
+        Ok(Response::new(ValidationResponse {
+            valid: problems.is_empty(),
+            problems,
+        }))
+    }
 }
@@ -297,3 +297,60 @@ pub async fn fibonacci(args: Vec<ArgValue>) -> String {
         "Error: Expected an integer argument".to_string()
     }
 }
+
+// Fixtures for the integration tests. Any client could call them, so they are only built with the
+// `test-tasks` feature.
+
+#[cfg(feature = "test-tasks")]
+static FLAKY_ATTEMPTS: once_cell::sync::Lazy<dashmap::DashMap<String, i32>> =
+    once_cell::sync::Lazy::new(dashmap::DashMap::new);
+
+#[cfg(feature = "test-tasks")]
+#[sync_task]
+pub fn flaky(args: Vec<ArgValue>) -> Result<String> {
+    let (key, failures) = match (args.first(), args.get(1)) {
+        (Some(ArgValue::String(key)), Some(ArgValue::Int32(failures))) => (key.clone(), *failures),
+        _ => {
+            return Err(TaskError::InvalidArguments(
+                "Expected a String key and an Int32 failure count for flaky".to_string(),
+            ))
+        }
+    };
+
+    let permanent = matches!(args.get(2), Some(ArgValue::Bool(true)));
+
+    let mut attempts = FLAKY_ATTEMPTS.entry(key).or_insert(0);
+    *attempts += 1;
+    if *attempts <= failures {
+        let message = format!("Flaky failure {} of {}", *attempts, failures);
+        if permanent {
+            Err(TaskError::InvalidArguments(message))
+        } else {
+            Err(TaskError::ExecutionError(message))
+        }
+    } else {
+        Ok(format!("Succeeded after {} failures", failures))
+    }
+}
+
+#[cfg(feature = "test-tasks")]
+#[async_task]
+pub async fn sleep(args: Vec<ArgValue>) -> String {
+    let millis = match args.first() {
+        Some(ArgValue::Int32(n)) if *n > 0 => *n as u64,
+        _ => 0,
+    };
+    tokio::time::sleep(tokio::time::Duration::from_millis(millis)).await;
+    format!("Slept {} ms", millis)
+}
+
+#[cfg(feature = "test-tasks")]
+#[sync_task]
+pub fn sleep_blocking(args: Vec<ArgValue>) -> Result<String> {
+    let millis = match args.first() {
+        Some(ArgValue::Int32(n)) if *n > 0 => *n as u64,
+        _ => 0,
+    };
+    std::thread::sleep(std::time::Duration::from_millis(millis));
+    Ok(format!("Slept {} ms", millis))
+}
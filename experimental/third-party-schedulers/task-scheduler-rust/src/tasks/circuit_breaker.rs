@@ -0,0 +1,172 @@
+use crate::error::{Result, TaskError};
+use crate::info_log;
+use crate::warn_log;
+use dashmap::DashMap;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy)]
+pub struct CircuitBreakerConfig {
+    /// Consecutive failures that open the circuit, 0 (the default) disables the breaker
+    pub failure_threshold: u32,
+    /// How long an open circuit rejects tasks before letting a probe through
+    pub open_duration: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            failure_threshold: 0,
+            open_duration: Duration::from_secs(30),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    /// A single probe task is running to decide whether to close the circuit again
+    HalfOpen,
+}
+
+#[derive(Default)]
+struct MethodCircuit {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    probing: bool,
+}
+
+pub struct CircuitBreaker {
+    config: RwLock<CircuitBreakerConfig>,
+    circuits: DashMap<String, MethodCircuit, ahash::RandomState>,
+}
+
+impl Default for CircuitBreaker {
+    fn default() -> Self {
+        Self {
+            config: RwLock::new(CircuitBreakerConfig::default()),
+            circuits: DashMap::with_hasher(ahash::RandomState::new()),
+        }
+    }
+}
+
+impl CircuitBreaker {
+    pub fn configure(&self, config: CircuitBreakerConfig) {
+        *self.config.write() = config;
+        self.circuits.clear();
+    }
+
+    /// Admits a task unless the method's circuit is open, letting a single probe through once
+    /// the open duration has elapsed. The returned permit must be given the task's outcome.
+    pub fn acquire<'a>(&'a self, method: &'a str) -> Result<CircuitPermit<'a>> {
+        let open_duration = self.config.read().open_duration;
+        if let Some(mut circuit) = self.circuits.get_mut(method) {
+            if let Some(opened_at) = circuit.opened_at {
+                if circuit.probing || opened_at.elapsed() < open_duration {
+                    return Err(TaskError::CircuitOpen(format!(
+                        "Method '{}' is failing repeatedly, rejecting tasks for up to {}s",
+                        method,
+                        open_duration.as_secs()
+                    )));
+                }
+
+                circuit.probing = true;
+                info_log!("Circuit breaker for '{}' is half-open, probing", method);
+            }
+        }
+
+        Ok(CircuitPermit {
+            breaker: self,
+            method,
+            recorded: false,
+        })
+    }
+
+    /// State of every method that failed since its circuit last closed, keyed by method.
+    pub fn states(&self) -> HashMap<String, CircuitState> {
+        self.circuits
+            .iter()
+            .map(|circuit| {
+                let state = match (circuit.opened_at, circuit.probing) {
+                    (None, _) => CircuitState::Closed,
+                    (Some(_), false) => CircuitState::Open,
+                    (Some(_), true) => CircuitState::HalfOpen,
+                };
+                (circuit.key().clone(), state)
+            })
+            .collect()
+    }
+
+    /// Records the outcome of a task. Transient failures count towards opening the circuit,
+    /// permanent errors (such as invalid arguments) only release a pending probe.
+    fn record_result(&self, method: &str, result: &Result<String>) {
+        match result {
+            Ok(_) => {
+                if let Some((_, circuit)) = self.circuits.remove(method) {
+                    if circuit.opened_at.is_some() {
+                        info_log!("Circuit breaker for '{}' closed", method);
+                    }
+                }
+            }
+            Err(err) if err.is_retryable() => self.record_failure(method),
+            Err(_) => {
+                if let Some(mut circuit) = self.circuits.get_mut(method) {
+                    circuit.probing = false;
+                }
+            }
+        }
+    }
+
+    fn record_failure(&self, method: &str) {
+        let failure_threshold = self.config.read().failure_threshold;
+        if failure_threshold == 0 {
+            return;
+        }
+
+        let mut circuit = self.circuits.entry(method.to_string()).or_default();
+        circuit.consecutive_failures += 1;
+        if circuit.probing {
+            circuit.probing = false;
+            circuit.opened_at = Some(Instant::now());
+            warn_log!("Circuit breaker for '{}' probe failed, reopening", method);
+        } else if circuit.opened_at.is_none() && circuit.consecutive_failures >= failure_threshold {
+            circuit.opened_at = Some(Instant::now());
+            warn_log!(
+                "Circuit breaker for '{}' opened after {} consecutive failures",
+                method,
+                circuit.consecutive_failures
+            );
+        }
+    }
+}
+
+/// A task admitted by [`CircuitBreaker::acquire`]. Dropping it without recording an outcome,
+/// e.g. when the task's future is cancelled by a client deadline or disconnect, counts as a
+/// failure, so a probe can never stay pending.
+#[must_use]
+pub struct CircuitPermit<'a> {
+    breaker: &'a CircuitBreaker,
+    method: &'a str,
+    recorded: bool,
+}
+
+impl CircuitPermit<'_> {
+    pub fn record_result(mut self, result: &Result<String>) {
+        self.recorded = true;
+        self.breaker.record_result(self.method, result);
+    }
+}
+
+impl Drop for CircuitPermit<'_> {
+    fn drop(&mut self) {
+        if !self.recorded {
+            warn_log!(
+                "Task for '{}' was cancelled before finishing, counting it as a failure",
+                self.method
+            );
+            self.breaker.record_failure(self.method);
+        }
+    }
+}
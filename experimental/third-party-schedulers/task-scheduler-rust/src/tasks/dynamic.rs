@@ -4,12 +4,15 @@ use crate::info_log;
 use crate::models::ArgValue;
 use crate::tasks::REGISTRY;
 use crate::warn_log;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
 use libloading::{Library, Symbol};
 use parking_lot::{Mutex, RwLock};
 use std::collections::HashMap;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tempfile::NamedTempFile;
 
 pub type SyncTaskFnPtr = unsafe fn(Vec<ArgValue>) -> Result<String>;
 pub type AsyncTaskFnPtr =
@@ -17,6 +20,14 @@ pub type AsyncTaskFnPtr =
 pub type TaskEntryInfo = (&'static str, bool, usize);
 
 pub type InitPluginFnPtr = unsafe fn() -> &'static [TaskEntryInfo];
+pub type PluginAbiVersionFnPtr = unsafe fn() -> u32;
+
+/// ABI version plugins must report through `plugin_abi_version` to be loaded.
+///
+/// Bump it whenever a type crossing the plugin boundary changes, such as `TaskError`, `ArgValue`
+/// or the task and `init_plugin` signatures above, since a plugin built against the old layout
+/// would otherwise still pass the check.
+pub const PLUGIN_ABI_VERSION: u32 = 2;
 
 pub struct DynamicLibraryInfo {
     pub path: PathBuf,
@@ -28,6 +39,8 @@ pub struct DynamicLibraryInfo {
 pub struct DynamicTaskLoader {
     libraries: RwLock<HashMap<String, DynamicLibraryInfo>>,
     plugin_dir: PathBuf,
+    trusted_keys: Option<Vec<VerifyingKey>>,
+    verified_copy_dir: Option<PathBuf>,
 }
 
 impl DynamicTaskLoader {
@@ -47,9 +60,25 @@ impl DynamicTaskLoader {
         Self {
             libraries: RwLock::new(HashMap::new()),
             plugin_dir,
+            trusted_keys: None,
+            verified_copy_dir: None,
         }
     }
 
+    /// Requires every plugin to carry a detached signature (`<library file>.sig`, hex encoded)
+    /// made by one of the given ed25519 keys. Unsigned or tampered plugins are refused.
+    pub fn with_trusted_keys(mut self, trusted_keys: Vec<VerifyingKey>) -> Self {
+        self.trusted_keys = Some(trusted_keys);
+        self
+    }
+
+    /// Writes the private copies verified plugins are loaded from to `dir` instead of the plugin
+    /// directory. It must allow executing mapped files, so it should not be a `noexec` mount.
+    pub fn with_verified_copy_dir(mut self, dir: PathBuf) -> Self {
+        self.verified_copy_dir = Some(dir);
+        self
+    }
+
     fn get_current_timestamp() -> u64 {
         SystemTime::now()
             .duration_since(UNIX_EPOCH)
@@ -90,11 +119,48 @@ impl DynamicTaskLoader {
         #[cfg(not(any(target_os = "windows", target_os = "macos")))]
         let lib_path = self.plugin_dir.join(format!("lib{}.so", name));
 
+        // A verified plugin is loaded from a private copy of the bytes that were checked, so the
+        // file in the plugin directory cannot be swapped between verification and loading
+        let verified_copy = match &self.trusted_keys {
+            Some(trusted_keys) => {
+                let library_bytes = std::fs::read(&lib_path).map_err(|e| {
+                    TaskError::ExecutionError(format!(
+                        "Failed to read dynamic library '{}' for verification: {}",
+                        name, e
+                    ))
+                })?;
+                Self::verify_signature(name, &lib_path, &library_bytes, trusted_keys)?;
+                Some(self.private_copy(name, &lib_path, &library_bytes)?)
+            }
+            None => None,
+        };
+        let load_path = verified_copy
+            .as_ref()
+            .map_or(lib_path.as_path(), NamedTempFile::path);
+
         info_log!("Loading dynamic library from: {}", lib_path.display());
 
-        let lib = unsafe { Library::new(&lib_path) }.map_err(|e| {
+        let lib = unsafe { Library::new(load_path) }.map_err(|e| {
             TaskError::ExecutionError(format!("Failed to load dynamic library '{}': {}", name, e))
         })?;
+        // The library stays mapped once loaded, so its private copy can be removed right away
+        drop(verified_copy);
+
+        let abi_version_fn: Symbol<PluginAbiVersionFnPtr> =
+            unsafe { lib.get(b"plugin_abi_version") }.map_err(|e| {
+                TaskError::ExecutionError(format!(
+                    "Plugin '{}' missing 'plugin_abi_version' symbol: {}",
+                    name, e
+                ))
+            })?;
+
+        let abi_version = unsafe { abi_version_fn() };
+        if abi_version != PLUGIN_ABI_VERSION {
+            return Err(TaskError::ExecutionError(format!(
+                "Plugin '{}' was built for ABI version {}, but the host expects version {}",
+                name, abi_version, PLUGIN_ABI_VERSION
+            )));
+        }
 
         let init_fn: Symbol<InitPluginFnPtr> = unsafe { lib.get(b"init_plugin") }.map_err(|e| {
             TaskError::ExecutionError(format!(
@@ -159,14 +225,94 @@ impl DynamicTaskLoader {
         Ok(registered)
     }
 
+    fn verify_signature(
+        name: &str,
+        lib_path: &Path,
+        library_bytes: &[u8],
+        trusted_keys: &[VerifyingKey],
+    ) -> Result<()> {
+        let mut sig_path = lib_path.as_os_str().to_owned();
+        sig_path.push(".sig");
+        let sig_path = PathBuf::from(sig_path);
+
+        let signature_hex = std::fs::read_to_string(&sig_path).map_err(|e| {
+            TaskError::ExecutionError(format!(
+                "Plugin '{}' has no readable signature file '{}': {}",
+                name,
+                sig_path.display(),
+                e
+            ))
+        })?;
+        let signature = decode_hex(signature_hex.trim())
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or_else(|| {
+                TaskError::ExecutionError(format!(
+                    "Plugin '{}' signature file '{}' is malformed",
+                    name,
+                    sig_path.display()
+                ))
+            })?;
+
+        if trusted_keys
+            .iter()
+            .any(|key| key.verify(library_bytes, &signature).is_ok())
+        {
+            info_log!("Verified signature of plugin '{}'", name);
+            Ok(())
+        } else {
+            Err(TaskError::ExecutionError(format!(
+                "Plugin '{}' signature does not match any trusted key",
+                name
+            )))
+        }
+    }
+
+    /// Writes the library to a new hidden file only this process can write to, keeping its
+    /// extension since some platforms rely on it when loading. The copy goes next to the plugin
+    /// rather than into the system temp directory, which is often mounted `noexec`.
+    fn private_copy(
+        &self,
+        name: &str,
+        lib_path: &Path,
+        library_bytes: &[u8],
+    ) -> Result<NamedTempFile> {
+        let copy_dir = self.verified_copy_dir.as_ref().unwrap_or(&self.plugin_dir);
+        let suffix = lib_path
+            .extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+
+        let write_copy = || -> std::io::Result<NamedTempFile> {
+            let mut copy = tempfile::Builder::new()
+                .prefix(&format!(".{}-", name))
+                .suffix(&suffix)
+                .tempfile_in(copy_dir)?;
+            copy.write_all(library_bytes)?;
+            Ok(copy)
+        };
+        write_copy().map_err(|e| {
+            TaskError::ExecutionError(format!(
+                "Failed to copy verified plugin '{}' for loading: {}",
+                name, e
+            ))
+        })
+    }
+
     pub fn unload_plugin(&self, name: &str) -> Result<Vec<String>> {
         let mut libraries = self.libraries.write();
 
-        if let Some(lib_info) = libraries.remove(name) {
-            for task_name in &lib_info.registered_tasks {
-                REGISTRY.unregister_task(task_name);
-            }
+        if let Some(lib_info) = libraries.get(name) {
+            REGISTRY
+                .unregister_idle_tasks(&lib_info.registered_tasks)
+                .map_err(|active_tasks| {
+                    TaskError::PluginInUse(format!(
+                        "Plugin '{}' has {} tasks still executing",
+                        name, active_tasks
+                    ))
+                })?;
+        }
 
+        if let Some(lib_info) = libraries.remove(name) {
             info_log!(
                 "Unloaded plugin '{}' with {} tasks",
                 name,
@@ -198,7 +344,9 @@ impl DynamicTaskLoader {
 
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.is_file() {
+            // Hidden files include the private copies of verified plugins being loaded
+            let is_hidden = entry.file_name().to_string_lossy().starts_with('.');
+            if path.is_file() && !is_hidden {
                 let extension = path.extension().and_then(|e| e.to_str());
 
                 let is_lib = match extension {
@@ -259,3 +407,53 @@ pub fn init_dynamic_loader(plugin_dir: PathBuf) {
     let mut loader = DYNAMIC_LOADER.lock();
     *loader = Some(DynamicTaskLoader::new(plugin_dir));
 }
+
+pub fn init_dynamic_loader_with_trusted_keys(
+    plugin_dir: PathBuf,
+    trusted_keys: Vec<VerifyingKey>,
+    verified_copy_dir: Option<PathBuf>,
+) {
+    let mut loader = DYNAMIC_LOADER.lock();
+    let mut dynamic_loader = DynamicTaskLoader::new(plugin_dir).with_trusted_keys(trusted_keys);
+    if let Some(dir) = verified_copy_dir {
+        dynamic_loader = dynamic_loader.with_verified_copy_dir(dir);
+    }
+    *loader = Some(dynamic_loader);
+}
+
+/// Reads hex encoded ed25519 public keys, one per line. Blank lines and lines starting with `#`
+/// are ignored.
+pub fn load_trusted_keys<P: AsRef<Path>>(path: P) -> Result<Vec<VerifyingKey>> {
+    let path = path.as_ref();
+    let content = std::fs::read_to_string(path).map_err(|e| {
+        TaskError::ExecutionError(format!(
+            "Failed to read trusted plugin keys '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| {
+            decode_hex(line)
+                .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+                .and_then(|bytes| VerifyingKey::from_bytes(&bytes).ok())
+                .ok_or_else(|| {
+                    TaskError::InvalidArguments(format!("Invalid trusted plugin key: {}", line))
+                })
+        })
+        .collect()
+}
+
+fn decode_hex(hex: &str) -> Option<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.is_ascii() {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).ok())
+        .collect()
+}
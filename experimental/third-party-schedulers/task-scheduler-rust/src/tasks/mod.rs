@@ -4,8 +4,10 @@ use std::collections::HashMap;
 use std::sync::Mutex;
 
 pub mod builtin;
+pub mod circuit_breaker;
 pub mod dynamic;
 mod registry;
+pub mod retry_budget;
 
 pub use crate::tasks::taskscheduler::*;
 pub use dynamic::DYNAMIC_LOADER;
@@ -85,6 +87,9 @@ pub fn reload_all_plugins() -> crate::error::Result<HashMap<String, Vec<String>>
 
 pub mod taskscheduler {
     tonic::include_proto!("taskscheduler");
+
+    pub const FILE_DESCRIPTOR_SET: &[u8] =
+        tonic::include_file_descriptor_set!("task_scheduler_descriptor");
 }
 
 pub trait Task: Send + Sync {
@@ -5,18 +5,25 @@ use crate::models::wrappers::{
 };
 use crate::models::ArgValue;
 use crate::models::TaskResult;
+use crate::tasks::circuit_breaker::CircuitBreaker;
+use crate::tasks::retry_budget::RetryBudget;
+use crate::tasks::taskscheduler::arg_binding::ValueType;
+use crate::tasks::taskscheduler::task_request::DependencyPolicy;
 use crate::tasks::taskscheduler::{self, ListValue, MapValue};
 use crate::warn_log;
 use dashmap::DashMap;
 use lru::LruCache;
 use once_cell::sync::Lazy;
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock};
 use prost::Message;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::future::Future;
 use std::num::NonZeroUsize;
 use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
 type TaskFn = fn(Vec<ArgValue>) -> TaskResultType<String>;
 type AsyncTaskFn = fn(Vec<ArgValue>) -> Pin<Box<dyn std::future::Future<Output = String> + Send>>;
@@ -26,23 +33,55 @@ pub type DynamicAsyncTaskFn =
     unsafe fn(Vec<ArgValue>) -> Pin<Box<dyn Future<Output = String> + Send>>;
 
 const CACHE_SIZE: usize = 1000;
+const DEFAULT_TIMEOUT_SECONDS: u64 = 3600;
+const MAX_TIMEOUT_SECONDS: u64 = 86400;
+const MAX_RETRIES: u32 = 10;
+const DEFAULT_MAX_ARGS_SIZE: usize = 1024 * 1024;
+const DEFAULT_SOFT_TIMEOUT_PERCENT: u8 = 80;
+const DEFAULT_MAX_SYNC_THREADS: usize = 256;
+
+// Plugin functions are only called while their method is marked active, which keeps the plugin
+// from being unloaded, so the library they point into stays loaded for the whole call.
+#[derive(Clone, Copy)]
+enum SyncTaskFunc {
+    Builtin(TaskFn),
+    Dynamic(DynamicSyncTaskFn),
+}
+
+impl SyncTaskFunc {
+    fn call(self, args: Vec<ArgValue>) -> TaskResultType<String> {
+        match self {
+            Self::Builtin(func) => func(args),
+            Self::Dynamic(func) => unsafe { func(args) },
+        }
+    }
+}
 
-lazy_static::lazy_static! {
-    static ref DYNAMIC_SYNC_FUNCTIONS: DashMap<String, DynamicSyncTaskFn> = DashMap::new();
-    static ref DYNAMIC_ASYNC_FUNCTIONS: DashMap<String, DynamicAsyncTaskFn> = DashMap::new();
-    static ref FUNCTION_REGISTER_TIMES: DashMap<String, u64> = DashMap::new();
+#[derive(Clone, Copy)]
+enum AsyncTaskFunc {
+    Builtin(AsyncTaskFn),
+    Dynamic(DynamicAsyncTaskFn),
+}
+
+impl AsyncTaskFunc {
+    fn call(self, args: Vec<ArgValue>) -> Pin<Box<dyn Future<Output = String> + Send>> {
+        match self {
+            Self::Builtin(func) => func(args),
+            Self::Dynamic(func) => unsafe { func(args) },
+        }
+    }
 }
 
 #[derive(Clone, Copy)]
 struct SyncTaskInfo {
-    func: TaskFn,
+    func: SyncTaskFunc,
     register_time: u64,
     dynamic_lib: bool,
 }
 
 #[derive(Clone, Copy)]
 struct AsyncTaskInfo {
-    func: AsyncTaskFn,
+    func: AsyncTaskFunc,
     register_time: u64,
     dynamic_lib: bool,
 }
@@ -52,6 +91,55 @@ pub struct TaskRegistry {
     async_tasks: DashMap<String, AsyncTaskInfo, ahash::RandomState>,
     results_cache: Arc<[Mutex<LruCache<String, TaskResult>>; 32]>,
     cache_hasher: ahash::RandomState,
+    active_tasks: Arc<DashMap<String, usize, ahash::RandomState>>,
+    unload_lock: RwLock<()>,
+    circuit_breaker: CircuitBreaker,
+    retry_budget: RetryBudget,
+    max_args_size: AtomicUsize,
+    abandoned_threads: AtomicUsize,
+    sync_threads: Arc<AtomicUsize>,
+    max_sync_threads: AtomicUsize,
+    soft_timeout_percent: AtomicU8,
+    timeout_warnings: AtomicUsize,
+    aliases: DashMap<String, MethodAlias, ahash::RandomState>,
+}
+
+struct MethodAlias {
+    method: String,
+    uses: AtomicU64,
+}
+
+struct ActiveTaskGuard {
+    active_tasks: Arc<DashMap<String, usize, ahash::RandomState>>,
+    method: String,
+}
+
+impl ActiveTaskGuard {
+    fn new(active_tasks: Arc<DashMap<String, usize, ahash::RandomState>>, method: &str) -> Self {
+        *active_tasks.entry(method.to_string()).or_insert(0) += 1;
+        Self {
+            active_tasks,
+            method: method.to_string(),
+        }
+    }
+}
+
+impl Drop for ActiveTaskGuard {
+    fn drop(&mut self) {
+        self.active_tasks.remove_if_mut(&self.method, |_, count| {
+            *count -= 1;
+            *count == 0
+        });
+    }
+}
+
+/// Counts a sync task thread as alive until it returns, even once its task was abandoned.
+struct SyncThreadGuard(Arc<AtomicUsize>);
+
+impl Drop for SyncThreadGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
 }
 
 impl Default for TaskRegistry {
@@ -65,6 +153,17 @@ impl Default for TaskRegistry {
             async_tasks: DashMap::with_hasher(ahash::RandomState::new()),
             results_cache: Arc::new(caches),
             cache_hasher: ahash::RandomState::new(),
+            active_tasks: Arc::new(DashMap::with_hasher(ahash::RandomState::new())),
+            unload_lock: RwLock::new(()),
+            circuit_breaker: CircuitBreaker::default(),
+            retry_budget: RetryBudget::default(),
+            max_args_size: AtomicUsize::new(DEFAULT_MAX_ARGS_SIZE),
+            abandoned_threads: AtomicUsize::new(0),
+            sync_threads: Arc::new(AtomicUsize::new(0)),
+            max_sync_threads: AtomicUsize::new(DEFAULT_MAX_SYNC_THREADS),
+            soft_timeout_percent: AtomicU8::new(DEFAULT_SOFT_TIMEOUT_PERCENT),
+            timeout_warnings: AtomicUsize::new(0),
+            aliases: DashMap::with_hasher(ahash::RandomState::new()),
         }
     }
 }
@@ -89,7 +188,7 @@ impl TaskRegistry {
         self.sync_tasks.insert(
             name.to_string(),
             SyncTaskInfo {
-                func,
+                func: SyncTaskFunc::Builtin(func),
                 register_time: current_time,
                 dynamic_lib: false,
             },
@@ -115,7 +214,7 @@ impl TaskRegistry {
         self.async_tasks.insert(
             name.to_string(),
             AsyncTaskInfo {
-                func,
+                func: AsyncTaskFunc::Builtin(func),
                 register_time: current_time,
                 dynamic_lib: false,
             },
@@ -148,13 +247,10 @@ impl TaskRegistry {
             );
         }
 
-        DYNAMIC_SYNC_FUNCTIONS.insert(name.to_string(), func);
-        FUNCTION_REGISTER_TIMES.insert(name.to_string(), register_time);
-
         self.sync_tasks.insert(
             name.to_string(),
             SyncTaskInfo {
-                func: dynamic_sync_wrapper,
+                func: SyncTaskFunc::Dynamic(func),
                 register_time,
                 dynamic_lib: true,
             },
@@ -189,13 +285,10 @@ impl TaskRegistry {
             );
         }
 
-        DYNAMIC_ASYNC_FUNCTIONS.insert(name.to_string(), func);
-        FUNCTION_REGISTER_TIMES.insert(name.to_string(), register_time);
-
         self.async_tasks.insert(
             name.to_string(),
             AsyncTaskInfo {
-                func: dynamic_async_wrapper,
+                func: AsyncTaskFunc::Dynamic(func),
                 register_time,
                 dynamic_lib: true,
             },
@@ -208,10 +301,6 @@ impl TaskRegistry {
         let removed_sync = self.sync_tasks.remove(name);
         let removed_async = self.async_tasks.remove(name);
 
-        DYNAMIC_SYNC_FUNCTIONS.remove(name);
-        DYNAMIC_ASYNC_FUNCTIONS.remove(name);
-        FUNCTION_REGISTER_TIMES.remove(name);
-
         if removed_sync.is_some() || removed_async.is_some() {
             let task_type = if removed_sync.is_some() {
                 "sync"
@@ -250,6 +339,127 @@ impl TaskRegistry {
         tasks
     }
 
+    pub fn register_alias(&self, alias: &str, method: &str) {
+        self.aliases.insert(
+            alias.to_string(),
+            MethodAlias {
+                method: method.to_string(),
+                uses: AtomicU64::new(0),
+            },
+        );
+    }
+
+    /// Tasks executed through each alias, keyed by alias. Validating a task does not count.
+    pub fn alias_use_counts(&self) -> HashMap<String, u64> {
+        self.aliases
+            .iter()
+            .map(|alias| (alias.key().clone(), alias.uses.load(Ordering::Relaxed)))
+            .collect()
+    }
+
+    fn resolve_alias<'a>(
+        &self,
+        task: &'a taskscheduler::TaskRequest,
+    ) -> Cow<'a, taskscheduler::TaskRequest> {
+        match self.aliases.get(&task.method) {
+            Some(alias) => Cow::Owned(taskscheduler::TaskRequest {
+                method: alias.method.clone(),
+                ..task.clone()
+            }),
+            None => Cow::Borrowed(task),
+        }
+    }
+
+    /// Counts and warns about a task about to run through a deprecated alias.
+    fn record_alias_use(&self, task: &taskscheduler::TaskRequest) {
+        if let Some(alias) = self.aliases.get(&task.method) {
+            alias.uses.fetch_add(1, Ordering::Relaxed);
+            warn_log!(
+                "Task '{}' uses deprecated method '{}', running '{}' instead",
+                task.task_id,
+                task.method,
+                alias.method
+            );
+        }
+    }
+
+    pub fn set_max_args_size(&self, bytes: usize) {
+        self.max_args_size.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Sync task threads abandoned on timeout since startup, whether or not they returned since.
+    pub fn abandoned_thread_count(&self) -> usize {
+        self.abandoned_threads.load(Ordering::Relaxed)
+    }
+
+    /// Caps the sync task threads alive at once, abandoned ones included (0 disables the cap).
+    pub fn set_max_sync_threads(&self, max_threads: usize) {
+        self.max_sync_threads.store(max_threads, Ordering::Relaxed);
+    }
+
+    pub fn sync_thread_count(&self) -> usize {
+        self.sync_threads.load(Ordering::Relaxed)
+    }
+
+    /// Reserves a thread for a sync task, unless the cap is reached by running and abandoned ones.
+    fn reserve_sync_thread(&self) -> TaskResultType<SyncThreadGuard> {
+        let max_threads = self.max_sync_threads.load(Ordering::Relaxed);
+        self.sync_threads
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |threads| {
+                (max_threads == 0 || threads < max_threads).then_some(threads + 1)
+            })
+            .map(|_| SyncThreadGuard(Arc::clone(&self.sync_threads)))
+            .map_err(|threads| {
+                TaskError::ResourceExhausted(format!(
+                    "Sync task thread limit of {} reached, {} are still running",
+                    max_threads, threads
+                ))
+            })
+    }
+
+    pub fn set_soft_timeout_percent(&self, percent: u8) {
+        self.soft_timeout_percent.store(percent, Ordering::Relaxed);
+    }
+
+    pub fn timeout_warning_count(&self) -> usize {
+        self.timeout_warnings.load(Ordering::Relaxed)
+    }
+
+    pub fn circuit_breaker(&self) -> &CircuitBreaker {
+        &self.circuit_breaker
+    }
+
+    pub fn retry_budget(&self) -> &RetryBudget {
+        &self.retry_budget
+    }
+
+    pub fn active_task_count(&self, name: &str) -> usize {
+        self.active_tasks.get(name).map(|count| *count).unwrap_or(0)
+    }
+
+    /// Unregisters the tasks unless any of them is running, returning the running count if so.
+    /// Tasks cannot start while this runs, so once it succeeds none of them can still reach
+    /// their functions and the plugin providing them can be unloaded.
+    pub fn unregister_idle_tasks(&self, names: &[String]) -> Result<(), usize> {
+        let _unload_lock = self.unload_lock.write();
+        let active: usize = names.iter().map(|name| self.active_task_count(name)).sum();
+        if active > 0 {
+            return Err(active);
+        }
+
+        for name in names {
+            self.unregister_task(name);
+        }
+        Ok(())
+    }
+
+    /// Marks the method as running before its function is looked up, so its plugin cannot be
+    /// unloaded in between.
+    fn start_task(&self, method: &str) -> ActiveTaskGuard {
+        let _unload_lock = self.unload_lock.read();
+        ActiveTaskGuard::new(Arc::clone(&self.active_tasks), method)
+    }
+
     #[inline]
     fn get_cache_shard(&self, task_id: &str) -> &Mutex<LruCache<String, TaskResult>> {
         let idx = self.cache_hasher.hash_one(task_id) as usize % 32;
@@ -357,45 +567,406 @@ impl TaskRegistry {
     }
 
     pub async fn execute_task(&self, task: &taskscheduler::TaskRequest) -> TaskResultType<String> {
+        self.record_alias_use(task);
+        let task = self.resolve_alias(task);
+        let task = task.as_ref();
+        let timeout = Self::resolve_timeout(task)?;
+        Self::validate_max_retries(task)?;
+        self.validate_args_size(task)?;
         let args_converted = Self::convert_args(&task.args)?;
 
-        if !task.deps.is_empty() {
-            for dep in &task.deps {
-                let shard = self.get_cache_shard(dep);
-                let cache = shard.lock();
-                if !cache.contains(dep.as_str()) {
-                    return Err(TaskError::MissingDependency(format!(
-                        "Dependency '{}' not found or not completed",
-                        dep
-                    )));
+        self.check_dependencies(task)?;
+
+        let args_converted = self.bind_dependency_results(task, args_converted)?;
+
+        let circuit_permit = self.circuit_breaker.acquire(&task.method)?;
+
+        let _active_guard = self.start_task(&task.method);
+        let outcome = self.run_with_retries(task, args_converted, timeout).await;
+        circuit_permit.record_result(&outcome);
+        let task_fn_result = outcome?;
+
+        // Fire-and-forget tasks only record their completion so dependents can still run
+        let task_result_for_cache = if task.persist_result.unwrap_or(true) {
+            TaskResult {
+                status: 1,
+                value: task_fn_result.clone(),
+            }
+        } else {
+            TaskResult {
+                status: taskscheduler::task_response::Status::NotPersisted as i32,
+                value: String::new(),
+            }
+        };
+        self.cache_task_result(task.task_id.clone(), task_result_for_cache)
+            .await;
+
+        Ok(task_fn_result)
+    }
+
+    pub fn validate_task(&self, task: &taskscheduler::TaskRequest) -> Vec<TaskError> {
+        let task = self.resolve_alias(task);
+        let task = task.as_ref();
+        let mut problems = Vec::new();
+
+        if let Err(err) = Self::resolve_timeout(task) {
+            problems.push(err);
+        }
+        if let Err(err) = Self::validate_max_retries(task) {
+            problems.push(err);
+        }
+        // Oversized arguments are not decoded, as on submission
+        if let Err(err) = self
+            .validate_args_size(task)
+            .and_then(|_| Self::convert_args(&task.args).map(|_| ()))
+        {
+            problems.push(err);
+        }
+
+        let method_exists = if task.is_async {
+            self.async_tasks.contains_key(&task.method)
+        } else {
+            self.sync_tasks.contains_key(&task.method)
+        };
+        if !method_exists {
+            problems.push(TaskError::MethodNotFound(task.method.clone()));
+        }
+
+        if let Err(err) = self.check_dependencies(task) {
+            problems.push(err);
+        }
+        problems.extend(
+            task.bindings
+                .iter()
+                .filter_map(|binding| Self::check_binding_declared(task, binding).err()),
+        );
+        if let Err(err) = Self::check_binding_positions(task) {
+            problems.push(err);
+        }
+
+        problems
+    }
+
+    async fn run_with_retries(
+        &self,
+        task: &taskscheduler::TaskRequest,
+        args_converted: Vec<ArgValue>,
+        timeout: Duration,
+    ) -> TaskResultType<String> {
+        let mut attempt = 0;
+        loop {
+            match self.run_task(task, args_converted.clone(), timeout).await {
+                Ok(value) => return Ok(value),
+                Err(err)
+                    if Self::should_retry(task, &err)
+                        && attempt < task.max_retries
+                        && self.retry_budget.try_acquire() =>
+                {
+                    attempt += 1;
+                    warn_log!(
+                        "Task '{}' failed on attempt {}/{}: {}. Retrying",
+                        task.task_id,
+                        attempt,
+                        task.max_retries + 1,
+                        err
+                    );
                 }
+                Err(err) => return Err(err),
             }
         }
+    }
+
+    /// A timed-out sync task is not retried, since each attempt would abandon another thread that
+    /// keeps running until the task returns on its own.
+    fn should_retry(task: &taskscheduler::TaskRequest, err: &TaskError) -> bool {
+        err.is_retryable() && (task.is_async || !matches!(err, TaskError::Timeout(_)))
+    }
 
-        let task_fn_result = if task.is_async {
+    async fn run_task(
+        &self,
+        task: &taskscheduler::TaskRequest,
+        args_converted: Vec<ArgValue>,
+        timeout: Duration,
+    ) -> TaskResultType<String> {
+        if task.is_async {
             let async_func = self
                 .async_tasks
                 .get(&task.method)
                 .map(|entry| entry.func)
                 .ok_or_else(|| TaskError::MethodNotFound(task.method.clone()))?;
-            async_func(args_converted).await
+            self.timeout_with_warning(task, timeout, async_func.call(args_converted))
+                .await
+                .map_err(|_| Self::timeout_error(task, timeout))
         } else {
             let sync_func = self
                 .sync_tasks
                 .get(&task.method)
                 .map(|entry| entry.func)
                 .ok_or_else(|| TaskError::MethodNotFound(task.method.clone()))?;
-            sync_func(args_converted)?
+
+            // A sync task cannot be cancelled, so it runs on its own thread which is abandoned
+            // on timeout. The thread keeps its method marked active until it actually returns.
+            let sync_thread = self.reserve_sync_thread()?;
+            let (sender, receiver) = tokio::sync::oneshot::channel();
+            let thread_guard = ActiveTaskGuard::new(Arc::clone(&self.active_tasks), &task.method);
+            std::thread::Builder::new()
+                .spawn(move || {
+                    let _sync_thread = sync_thread;
+                    let _thread_guard = thread_guard;
+                    let _ = sender.send(sync_func.call(args_converted));
+                })
+                .map_err(|e| {
+                    TaskError::ExecutionError(format!("Failed to spawn task thread: {}", e))
+                })?;
+
+            match self.timeout_with_warning(task, timeout, receiver).await {
+                Ok(Ok(result)) => result,
+                Ok(Err(_)) => Err(TaskError::ExecutionError(format!(
+                    "Task '{}' panicked",
+                    task.task_id
+                ))),
+                Err(_) => {
+                    let abandoned = self.abandoned_threads.fetch_add(1, Ordering::Relaxed) + 1;
+                    warn_log!(
+                        "Abandoned the thread of task '{}' after {}s ({} abandoned threads in total)",
+                        task.task_id,
+                        timeout.as_secs(),
+                        abandoned
+                    );
+                    Err(Self::timeout_error(task, timeout))
+                }
+            }
+        }
+    }
+
+    /// Like `tokio::time::timeout`, but warns once the soft timeout share of `timeout` elapses.
+    async fn timeout_with_warning<F: Future>(
+        &self,
+        task: &taskscheduler::TaskRequest,
+        timeout: Duration,
+        future: F,
+    ) -> Result<F::Output, tokio::time::error::Elapsed> {
+        let mut future = std::pin::pin!(tokio::time::timeout(timeout, future));
+        let percent = self.soft_timeout_percent.load(Ordering::Relaxed);
+        if percent == 0 || percent >= 100 {
+            return future.await;
+        }
+
+        let soft_timeout = timeout.mul_f64(f64::from(percent) / 100.0);
+        tokio::select! {
+            result = &mut future => return result,
+            _ = tokio::time::sleep(soft_timeout) => {}
+        }
+
+        let warnings = self.timeout_warnings.fetch_add(1, Ordering::Relaxed) + 1;
+        warn_log!(
+            "Task '{}' still running after {}ms, {}% of its {}s timeout ({} timeout warnings in total)",
+            task.task_id,
+            soft_timeout.as_millis(),
+            percent,
+            timeout.as_secs(),
+            warnings
+        );
+        future.await
+    }
+
+    fn timeout_error(task: &taskscheduler::TaskRequest, timeout: Duration) -> TaskError {
+        TaskError::Timeout(format!(
+            "Task '{}' exceeded {}s",
+            task.task_id,
+            timeout.as_secs()
+        ))
+    }
+
+    fn resolve_timeout(task: &taskscheduler::TaskRequest) -> TaskResultType<Duration> {
+        match task.timeout_seconds {
+            0 => Ok(Duration::from_secs(DEFAULT_TIMEOUT_SECONDS)),
+            seconds if seconds > MAX_TIMEOUT_SECONDS => Err(TaskError::InvalidArguments(format!(
+                "Timeout of {}s exceeds the maximum of {}s",
+                seconds, MAX_TIMEOUT_SECONDS
+            ))),
+            seconds => Ok(Duration::from_secs(seconds)),
+        }
+    }
+
+    fn validate_max_retries(task: &taskscheduler::TaskRequest) -> TaskResultType<()> {
+        if task.max_retries > MAX_RETRIES {
+            return Err(TaskError::InvalidArguments(format!(
+                "Max retries of {} exceeds the maximum of {}",
+                task.max_retries, MAX_RETRIES
+            )));
+        }
+        Ok(())
+    }
+
+    fn validate_args_size(&self, task: &taskscheduler::TaskRequest) -> TaskResultType<()> {
+        let max_args_size = self.max_args_size.load(Ordering::Relaxed);
+        if max_args_size == 0 {
+            return Ok(());
+        }
+
+        let args_size: usize = task.args.iter().map(|arg| arg.encoded_len()).sum();
+        if args_size > max_args_size {
+            return Err(TaskError::InvalidArguments(format!(
+                "Arguments are {} bytes, exceeding the limit of {} bytes",
+                args_size, max_args_size
+            )));
+        }
+        Ok(())
+    }
+
+    fn bind_dependency_results(
+        &self,
+        task: &taskscheduler::TaskRequest,
+        mut args: Vec<ArgValue>,
+    ) -> TaskResultType<Vec<ArgValue>> {
+        Self::check_binding_positions(task)?;
+
+        let mut bindings: Vec<&taskscheduler::ArgBinding> = task.bindings.iter().collect();
+        bindings.sort_by_key(|binding| binding.position);
+
+        for binding in bindings {
+            Self::check_binding_declared(task, binding)?;
+
+            let dep_result = self
+                .get_cache_shard(&binding.dep)
+                .lock()
+                .get(binding.dep.as_str())
+                .cloned()
+                .ok_or_else(|| {
+                    TaskError::MissingDependency(format!(
+                        "Dependency '{}' not found or not completed",
+                        binding.dep
+                    ))
+                })?;
+
+            if dep_result.status == taskscheduler::task_response::Status::NotPersisted as i32 {
+                return Err(TaskError::InvalidArguments(format!(
+                    "Dependency '{}' did not persist its result",
+                    binding.dep
+                )));
+            }
+
+            args.insert(
+                binding.position as usize,
+                Self::convert_bound_result(binding, &dep_result.value)?,
+            );
+        }
+
+        Ok(args)
+    }
+
+    fn check_dependencies(&self, task: &taskscheduler::TaskRequest) -> TaskResultType<()> {
+        let policy = task.dependency_policy();
+        let required = match policy {
+            DependencyPolicy::All => task.deps.len(),
+            DependencyPolicy::Any => task.deps.len().min(1),
+            DependencyPolicy::Quorum => {
+                let quorum = task.dependency_quorum as usize;
+                if quorum == 0 || quorum > task.deps.len() {
+                    return Err(TaskError::InvalidArguments(format!(
+                        "Dependency quorum of {} must be between 1 and the {} dependencies",
+                        quorum,
+                        task.deps.len()
+                    )));
+                }
+                quorum
+            }
         };
 
-        let task_result_for_cache = TaskResult {
-            status: 1,
-            value: task_fn_result.clone(),
+        // Failed dependencies are never cached, so they count as not completed
+        let missing: Vec<TaskError> = task
+            .deps
+            .iter()
+            .filter_map(|dep| self.check_dependency(dep).err())
+            .collect();
+        let completed = task.deps.len() - missing.len();
+        if completed >= required {
+            return Ok(());
+        }
+
+        match missing.into_iter().next() {
+            Some(err) if policy == DependencyPolicy::All => Err(err),
+            _ => Err(TaskError::MissingDependency(format!(
+                "{} of {} dependencies completed, {} required",
+                completed,
+                task.deps.len(),
+                required
+            ))),
+        }
+    }
+
+    fn check_dependency(&self, dep: &str) -> TaskResultType<()> {
+        if !self.get_cache_shard(dep).lock().contains(dep) {
+            return Err(TaskError::MissingDependency(format!(
+                "Dependency '{}' not found or not completed",
+                dep
+            )));
+        }
+        Ok(())
+    }
+
+    fn check_binding_declared(
+        task: &taskscheduler::TaskRequest,
+        binding: &taskscheduler::ArgBinding,
+    ) -> TaskResultType<()> {
+        if !task.deps.contains(&binding.dep) {
+            return Err(TaskError::InvalidArguments(format!(
+                "Binding references '{}' which is not a dependency",
+                binding.dep
+            )));
+        }
+        Ok(())
+    }
+
+    /// Bindings are inserted in ascending position order, so each position may be at most one
+    /// past the arguments present at that point.
+    fn check_binding_positions(task: &taskscheduler::TaskRequest) -> TaskResultType<()> {
+        let mut positions: Vec<u32> = task.bindings.iter().map(|b| b.position).collect();
+        positions.sort_unstable();
+
+        for (inserted, position) in positions.into_iter().enumerate() {
+            let max_position = task.args.len() + inserted;
+            if position as usize > max_position {
+                return Err(TaskError::InvalidArguments(format!(
+                    "Binding position {} is out of range, the task has {} arguments at that point",
+                    position, max_position
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    fn convert_bound_result(
+        binding: &taskscheduler::ArgBinding,
+        value: &str,
+    ) -> TaskResultType<ArgValue> {
+        let invalid = |type_name: &str| {
+            TaskError::InvalidArguments(format!(
+                "Result '{}' of dependency '{}' is not a valid {}",
+                value, binding.dep, type_name
+            ))
         };
-        self.cache_task_result(task.task_id.clone(), task_result_for_cache)
-            .await;
 
-        Ok(task_fn_result)
+        match binding.value_type() {
+            ValueType::String => Ok(ArgValue::String(value.to_string())),
+            ValueType::Int32 => value
+                .parse()
+                .map(ArgValue::Int32)
+                .map_err(|_| invalid("int32")),
+            ValueType::Int64 => value
+                .parse()
+                .map(ArgValue::Int64)
+                .map_err(|_| invalid("int64")),
+            ValueType::Double => value
+                .parse()
+                .map(ArgValue::Double)
+                .map_err(|_| invalid("double")),
+            ValueType::Bool => value
+                .parse()
+                .map(ArgValue::Bool)
+                .map_err(|_| invalid("bool")),
+        }
     }
 
     pub async fn execute_tasks(
@@ -427,41 +998,12 @@ pub async fn process_task(req: &taskscheduler::TaskRequest) -> TaskResult {
                 TaskError::InvalidArguments(a) => (2, format!("Invalid arguments: {}", a)),
                 TaskError::MissingDependency(d) => (2, format!("Missing dependency: {}", d)),
                 TaskError::ExecutionError(e) => (2, format!("Task execution failed: {}", e)),
+                TaskError::Timeout(t) => (2, format!("Task timed out: {}", t)),
+                TaskError::PluginInUse(p) => (2, format!("Plugin in use: {}", p)),
+                TaskError::CircuitOpen(c) => (2, format!("Circuit open: {}", c)),
+                TaskError::ResourceExhausted(r) => (2, format!("Resource exhausted: {}", r)),
             };
             TaskResult { status, value }
         }
     }
 }
-
-fn dynamic_sync_wrapper(args: Vec<ArgValue>) -> TaskResultType<String> {
-    for entry in DYNAMIC_SYNC_FUNCTIONS.iter() {
-        let dynamic_fn = entry.value();
-        match unsafe { dynamic_fn(args.clone()) } {
-            Ok(result) => return Ok(result),
-            Err(_) => continue,
-        }
-    }
-
-    Err(TaskError::MethodNotFound(
-        "Dynamic sync function not found or all functions failed".to_string(),
-    ))
-}
-
-fn dynamic_async_wrapper(args: Vec<ArgValue>) -> Pin<Box<dyn Future<Output = String> + Send>> {
-    let args_clone = args.clone();
-
-    let mut function_list = Vec::new();
-    for entry in DYNAMIC_ASYNC_FUNCTIONS.iter() {
-        function_list.push((entry.key().clone(), *entry.value()));
-    }
-
-    Box::pin(async move {
-        if !function_list.is_empty() {
-            let (_, dynamic_fn) = &function_list[0];
-            let future = unsafe { dynamic_fn(args_clone) };
-            return future.await;
-        }
-
-        "Error: No dynamic async functions registered".to_string()
-    })
-}
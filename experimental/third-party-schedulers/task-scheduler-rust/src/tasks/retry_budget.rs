@@ -0,0 +1,98 @@
+use crate::warn_log;
+use parking_lot::Mutex;
+use std::time::Instant;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryBudgetConfig {
+    /// Retries that can be spent in a burst, 0 disables the budget
+    pub capacity: u32,
+    /// Retries added back to the budget per second
+    pub refill_per_sec: f64,
+}
+
+impl Default for RetryBudgetConfig {
+    fn default() -> Self {
+        Self {
+            capacity: 100,
+            refill_per_sec: 10.0,
+        }
+    }
+}
+
+struct BudgetState {
+    config: RetryBudgetConfig,
+    tokens: f64,
+    refilled_at: Instant,
+    exhausted: bool,
+}
+
+impl BudgetState {
+    fn refill(&mut self) {
+        let now = Instant::now();
+        let elapsed = now.duration_since(self.refilled_at).as_secs_f64();
+        self.tokens =
+            (self.tokens + elapsed * self.config.refill_per_sec).min(self.config.capacity as f64);
+        self.refilled_at = now;
+    }
+}
+
+/// Token bucket shared by all tasks, so a correlated failure cannot turn into a retry storm.
+pub struct RetryBudget {
+    state: Mutex<BudgetState>,
+}
+
+impl Default for RetryBudget {
+    fn default() -> Self {
+        let config = RetryBudgetConfig::default();
+        Self {
+            state: Mutex::new(BudgetState {
+                config,
+                tokens: config.capacity as f64,
+                refilled_at: Instant::now(),
+                exhausted: false,
+            }),
+        }
+    }
+}
+
+impl RetryBudget {
+    pub fn configure(&self, config: RetryBudgetConfig) {
+        let mut state = self.state.lock();
+        state.config = config;
+        state.tokens = config.capacity as f64;
+        state.refilled_at = Instant::now();
+        state.exhausted = false;
+    }
+
+    /// Takes a token for a retry, returning false once the budget is exhausted.
+    pub fn try_acquire(&self) -> bool {
+        let mut state = self.state.lock();
+        if state.config.capacity == 0 {
+            return true;
+        }
+
+        state.refill();
+        if state.tokens >= 1.0 {
+            state.tokens -= 1.0;
+            state.exhausted = false;
+            return true;
+        }
+
+        if !state.exhausted {
+            state.exhausted = true;
+            warn_log!("Retry budget exhausted, failing tasks without retrying");
+        }
+        false
+    }
+
+    /// Whole retries currently left in the budget, `None` when the budget is disabled.
+    pub fn remaining(&self) -> Option<u32> {
+        let mut state = self.state.lock();
+        if state.config.capacity == 0 {
+            return None;
+        }
+
+        state.refill();
+        Some(state.tokens as u32)
+    }
+}
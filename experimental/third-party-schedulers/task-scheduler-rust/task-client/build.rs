@@ -0,0 +1,6 @@
+fn main() {
+    tonic_build::configure()
+        .build_server(false)
+        .compile_protos(&["../../proto/task_scheduler.proto"], &["../../proto"])
+        .unwrap_or_else(|e| panic!("Failed to compile protos: {}", e));
+}
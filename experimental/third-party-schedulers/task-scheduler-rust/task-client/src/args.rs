@@ -0,0 +1,133 @@
+use crate::taskscheduler::{ListValue, MapValue};
+use prost::Message;
+use prost_types::Any;
+use std::collections::HashMap;
+
+/// A task argument, mirroring the values the server decodes from `Any`.
+#[derive(Debug, Clone)]
+pub enum ArgValue {
+    Int32(i32),
+    Int64(i64),
+    UInt32(u32),
+    UInt64(u64),
+    Float(f32),
+    Double(f64),
+    Bool(bool),
+    String(String),
+    Bytes(Vec<u8>),
+    Array(Vec<ArgValue>),
+    Map(HashMap<String, ArgValue>),
+}
+
+impl From<i32> for ArgValue {
+    fn from(value: i32) -> Self {
+        ArgValue::Int32(value)
+    }
+}
+
+impl From<i64> for ArgValue {
+    fn from(value: i64) -> Self {
+        ArgValue::Int64(value)
+    }
+}
+
+impl From<u32> for ArgValue {
+    fn from(value: u32) -> Self {
+        ArgValue::UInt32(value)
+    }
+}
+
+impl From<u64> for ArgValue {
+    fn from(value: u64) -> Self {
+        ArgValue::UInt64(value)
+    }
+}
+
+impl From<f32> for ArgValue {
+    fn from(value: f32) -> Self {
+        ArgValue::Float(value)
+    }
+}
+
+impl From<f64> for ArgValue {
+    fn from(value: f64) -> Self {
+        ArgValue::Double(value)
+    }
+}
+
+impl From<bool> for ArgValue {
+    fn from(value: bool) -> Self {
+        ArgValue::Bool(value)
+    }
+}
+
+impl From<String> for ArgValue {
+    fn from(value: String) -> Self {
+        ArgValue::String(value)
+    }
+}
+
+impl From<&str> for ArgValue {
+    fn from(value: &str) -> Self {
+        ArgValue::String(value.to_string())
+    }
+}
+
+impl From<Vec<u8>> for ArgValue {
+    fn from(value: Vec<u8>) -> Self {
+        ArgValue::Bytes(value)
+    }
+}
+
+impl From<Vec<ArgValue>> for ArgValue {
+    fn from(value: Vec<ArgValue>) -> Self {
+        ArgValue::Array(value)
+    }
+}
+
+impl From<HashMap<String, ArgValue>> for ArgValue {
+    fn from(value: HashMap<String, ArgValue>) -> Self {
+        ArgValue::Map(value)
+    }
+}
+
+/// Encodes an argument into the `Any` message the server decodes it from.
+///
+/// prost encodes the scalar types the same way as their `google.protobuf` wrapper messages.
+pub fn encode_arg(value: ArgValue) -> Any {
+    fn wrap(type_name: &str, value: Vec<u8>) -> Any {
+        Any {
+            type_url: format!("type.googleapis.com/{}", type_name),
+            value,
+        }
+    }
+
+    match value {
+        ArgValue::Int32(value) => wrap("google.protobuf.Int32Value", value.encode_to_vec()),
+        ArgValue::Int64(value) => wrap("google.protobuf.Int64Value", value.encode_to_vec()),
+        ArgValue::UInt32(value) => wrap("google.protobuf.UInt32Value", value.encode_to_vec()),
+        ArgValue::UInt64(value) => wrap("google.protobuf.UInt64Value", value.encode_to_vec()),
+        ArgValue::Float(value) => wrap("google.protobuf.FloatValue", value.encode_to_vec()),
+        ArgValue::Double(value) => wrap("google.protobuf.DoubleValue", value.encode_to_vec()),
+        ArgValue::Bool(value) => wrap("google.protobuf.BoolValue", value.encode_to_vec()),
+        ArgValue::String(value) => wrap("google.protobuf.StringValue", value.encode_to_vec()),
+        ArgValue::Bytes(value) => wrap("google.protobuf.BytesValue", value.encode_to_vec()),
+        ArgValue::Array(values) => wrap(
+            "taskscheduler.ListValue",
+            ListValue {
+                values: values.into_iter().map(encode_arg).collect(),
+            }
+            .encode_to_vec(),
+        ),
+        ArgValue::Map(fields) => wrap(
+            "taskscheduler.MapValue",
+            MapValue {
+                fields: fields
+                    .into_iter()
+                    .map(|(key, value)| (key, encode_arg(value)))
+                    .collect(),
+            }
+            .encode_to_vec(),
+        ),
+    }
+}
@@ -0,0 +1,223 @@
+use crate::args::{encode_arg, ArgValue};
+use crate::taskscheduler::arg_binding::ValueType;
+use crate::taskscheduler::task_request::DependencyPolicy;
+use crate::taskscheduler::task_response;
+use crate::taskscheduler::task_scheduler_client::TaskSchedulerClient;
+use crate::taskscheduler::{ArgBinding, ResultRequest, ResultResponse, TaskRequest};
+use std::time::{Duration, Instant};
+use tonic::transport::Channel;
+use tonic::{Request, Status};
+
+const RESULT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Typed client for the task scheduler.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// use task_client::TaskClient;
+///
+/// let client = TaskClient::connect("http://127.0.0.1:50051").await?;
+/// let sum = client.submit("sum").method("add").arg(1).arg(2).send().await?;
+/// assert_eq!(sum, "3");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TaskClient {
+    inner: TaskSchedulerClient<Channel>,
+}
+
+impl TaskClient {
+    pub async fn connect(address: impl Into<String>) -> Result<Self, tonic::transport::Error> {
+        Ok(Self::new(
+            TaskSchedulerClient::connect(address.into()).await?,
+        ))
+    }
+
+    pub fn new(inner: TaskSchedulerClient<Channel>) -> Self {
+        Self { inner }
+    }
+
+    /// Starts building a task submission with the given task id.
+    pub fn submit(&self, task_id: impl Into<String>) -> TaskSubmission {
+        TaskSubmission {
+            client: self.inner.clone(),
+            request: TaskRequest {
+                task_id: task_id.into(),
+                ..Default::default()
+            },
+            deadline: None,
+        }
+    }
+
+    pub async fn get_result(&self, task_id: impl Into<String>) -> Result<ResultResponse, Status> {
+        let request = ResultRequest {
+            task_id: task_id.into(),
+        };
+        Ok(self
+            .inner
+            .clone()
+            .get_result(Request::new(request))
+            .await?
+            .into_inner())
+    }
+
+    /// Polls for the result of a task submitted elsewhere, e.g. by another client, until it is
+    /// no longer pending. Fails with `DEADLINE_EXCEEDED` once the timeout elapses.
+    ///
+    /// The server only caches completed tasks, so a task that failed keeps reporting `PENDING`
+    /// and is only noticed here as the timeout elapsing. Use [`TaskSubmission::send`] to observe
+    /// failures directly.
+    pub async fn wait_for_result(
+        &self,
+        task_id: impl Into<String>,
+        timeout: Duration,
+    ) -> Result<ResultResponse, Status> {
+        let task_id = task_id.into();
+        let start = Instant::now();
+        loop {
+            let result = self.get_result(task_id.clone()).await?;
+            if result.status != task_response::Status::Pending as i32 {
+                return Ok(result);
+            }
+            if start.elapsed() >= timeout {
+                return Err(Status::deadline_exceeded(format!(
+                    "Task '{}' still pending after {}ms",
+                    task_id,
+                    timeout.as_millis()
+                )));
+            }
+            tokio::time::sleep(RESULT_POLL_INTERVAL).await;
+        }
+    }
+}
+
+/// Builder for a single `SubmitTask` call, created by [`TaskClient::submit`].
+#[derive(Debug)]
+pub struct TaskSubmission {
+    client: TaskSchedulerClient<Channel>,
+    request: TaskRequest,
+    deadline: Option<Duration>,
+}
+
+impl TaskSubmission {
+    pub fn method(mut self, method: impl Into<String>) -> Self {
+        self.request.method = method.into();
+        self
+    }
+
+    pub fn arg(mut self, value: impl Into<ArgValue>) -> Self {
+        self.request.args.push(encode_arg(value.into()));
+        self
+    }
+
+    pub fn args<I, V>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = V>,
+        V: Into<ArgValue>,
+    {
+        self.request
+            .args
+            .extend(values.into_iter().map(|value| encode_arg(value.into())));
+        self
+    }
+
+    pub fn dep(mut self, task_id: impl Into<String>) -> Self {
+        self.request.deps.push(task_id.into());
+        self
+    }
+
+    pub fn deps<I, S>(mut self, task_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.request
+            .deps
+            .extend(task_ids.into_iter().map(Into::into));
+        self
+    }
+
+    /// Inserts the result of `dep` as a string at argument `position`, adding it as a dependency
+    /// if needed.
+    pub fn bind(self, dep: impl Into<String>, position: u32) -> Self {
+        self.bind_as(dep, position, ValueType::String)
+    }
+
+    /// Like [`bind`](Self::bind), but converts the result to `value_type` first.
+    pub fn bind_as(mut self, dep: impl Into<String>, position: u32, value_type: ValueType) -> Self {
+        let dep = dep.into();
+        if !self.request.deps.contains(&dep) {
+            self.request.deps.push(dep.clone());
+        }
+        self.request.bindings.push(ArgBinding {
+            dep,
+            position,
+            value_type: value_type as i32,
+        });
+        self
+    }
+
+    pub fn dependency_policy(mut self, policy: DependencyPolicy) -> Self {
+        self.request.set_dependency_policy(policy);
+        self
+    }
+
+    /// Runs the task once `quorum` of its dependencies have completed.
+    pub fn quorum(mut self, quorum: u32) -> Self {
+        self.request.set_dependency_policy(DependencyPolicy::Quorum);
+        self.request.dependency_quorum = quorum;
+        self
+    }
+
+    pub fn is_async(mut self, is_async: bool) -> Self {
+        self.request.is_async = is_async;
+        self
+    }
+
+    /// Sets the task timeout, rounded up to whole seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.request.timeout_seconds = timeout.as_secs() + u64::from(timeout.subsec_nanos() > 0);
+        self
+    }
+
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.request.max_retries = max_retries;
+        self
+    }
+
+    pub fn persist_result(mut self, persist_result: bool) -> Self {
+        self.request.persist_result = Some(persist_result);
+        self
+    }
+
+    /// Sets a gRPC deadline on the call, which also bounds the task execution on the server.
+    pub fn deadline(mut self, deadline: Duration) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Returns the request that [`send`](Self::send) would submit.
+    pub fn build(&self) -> TaskRequest {
+        self.request.clone()
+    }
+
+    /// Submits the task and waits for it to complete, returning its result.
+    pub async fn send(mut self) -> Result<String, Status> {
+        let mut request = Request::new(self.request);
+        if let Some(deadline) = self.deadline {
+            request.set_timeout(deadline);
+        }
+        Ok(self.client.submit_task(request).await?.into_inner().result)
+    }
+
+    /// Checks the task with `ValidateTask` without running it, returning the problems found.
+    pub async fn validate(mut self) -> Result<Vec<String>, Status> {
+        Ok(self
+            .client
+            .validate_task(Request::new(self.request))
+            .await?
+            .into_inner()
+            .problems)
+    }
+}
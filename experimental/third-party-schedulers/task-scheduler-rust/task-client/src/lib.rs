@@ -0,0 +1,9 @@
+pub mod args;
+pub mod client;
+
+pub use args::{encode_arg, ArgValue};
+pub use client::{TaskClient, TaskSubmission};
+
+pub mod taskscheduler {
+    tonic::include_proto!("taskscheduler");
+}
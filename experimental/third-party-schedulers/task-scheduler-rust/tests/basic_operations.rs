@@ -1,6 +1,9 @@
 mod common;
 
-use common::utils::{any_i32, connect_to_server, create_task_request};
+use common::utils::{admin_token_file, any_i32, connect_to_server, create_task_request, get_stats};
+use task_scheduler::tasks::taskscheduler::arg_binding::ValueType;
+use task_scheduler::tasks::taskscheduler::task_request::DependencyPolicy;
+use task_scheduler::tasks::taskscheduler::{task_response, ArgBinding, ResultRequest};
 use tonic::Request;
 
 #[tokio::test]
@@ -124,3 +127,412 @@ async fn test_dependencies() {
         "Unexpected result for delete_dep"
     );
 }
+
+#[tokio::test]
+async fn test_dependency_result_binding() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let parent_task = create_task_request(
+        "add_parent",
+        "add",
+        vec![any_i32(1), any_i32(2), any_i32(3)],
+        vec![],
+        false,
+    );
+    let response = client
+        .submit_task(Request::new(parent_task))
+        .await
+        .expect("Failed to submit parent task");
+    assert_eq!(response.into_inner().result, "6");
+
+    // The child receives the parent's result "6" as its first argument: 6 - 1 = 5
+    let mut child_task = create_task_request(
+        "remove_child",
+        "remove",
+        vec![any_i32(1)],
+        vec!["add_parent".to_string()],
+        false,
+    );
+    child_task.bindings = vec![ArgBinding {
+        dep: "add_parent".to_string(),
+        position: 0,
+        value_type: ValueType::Int32 as i32,
+    }];
+    let response = client
+        .submit_task(Request::new(child_task))
+        .await
+        .expect("Failed to submit child task");
+    assert_eq!(response.into_inner().result, "5");
+
+    // A binding to a parent that never completed fails the child
+    let mut orphan_task = create_task_request(
+        "remove_orphan",
+        "remove",
+        vec![any_i32(1)],
+        vec!["missing_parent".to_string()],
+        false,
+    );
+    orphan_task.bindings = vec![ArgBinding {
+        dep: "missing_parent".to_string(),
+        position: 0,
+        ..Default::default()
+    }];
+    let status = client
+        .submit_task(Request::new(orphan_task))
+        .await
+        .expect_err("Expected child of a missing parent to fail");
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+    // A binding must reference one of the declared dependencies
+    let mut undeclared_task = create_task_request(
+        "remove_undeclared",
+        "remove",
+        vec![any_i32(1)],
+        vec![],
+        false,
+    );
+    undeclared_task.bindings = vec![ArgBinding {
+        dep: "add_parent".to_string(),
+        position: 0,
+        ..Default::default()
+    }];
+    let status = client
+        .submit_task(Request::new(undeclared_task))
+        .await
+        .expect_err("Expected binding to an undeclared dependency to fail");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // Without a value type the result is bound as a string, even if it looks numeric
+    let mut string_task = create_task_request(
+        "echo_bound_string",
+        "echo_string",
+        vec![],
+        vec!["add_parent".to_string()],
+        false,
+    );
+    string_task.bindings = vec![ArgBinding {
+        dep: "add_parent".to_string(),
+        position: 0,
+        ..Default::default()
+    }];
+    let response = client
+        .submit_task(Request::new(string_task))
+        .await
+        .expect("Failed to submit string binding task");
+    assert_eq!(response.into_inner().result, "6");
+
+    // A result that does not convert to the requested type is rejected
+    let mut mistyped_task = create_task_request(
+        "add_mistyped",
+        "add",
+        vec![],
+        vec!["echo_bound_string".to_string()],
+        false,
+    );
+    mistyped_task.bindings = vec![ArgBinding {
+        dep: "echo_bound_string".to_string(),
+        position: 0,
+        value_type: ValueType::Bool as i32,
+    }];
+    let status = client
+        .submit_task(Request::new(mistyped_task))
+        .await
+        .expect_err("Expected a result of the wrong type to be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // Positions past the end of the arguments are rejected instead of clamped
+    let mut out_of_range_task = create_task_request(
+        "remove_out_of_range",
+        "remove",
+        vec![any_i32(1)],
+        vec!["add_parent".to_string()],
+        false,
+    );
+    out_of_range_task.bindings = vec![ArgBinding {
+        dep: "add_parent".to_string(),
+        position: 5,
+        value_type: ValueType::Int32 as i32,
+    }];
+    let status = client
+        .submit_task(Request::new(out_of_range_task))
+        .await
+        .expect_err("Expected an out of range binding to be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status
+        .message()
+        .contains("Binding position 5 is out of range"));
+}
+
+#[tokio::test]
+async fn test_fire_and_forget_result() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let mut parent_task = create_task_request(
+        "add_unpersisted",
+        "add",
+        vec![any_i32(1), any_i32(2)],
+        vec![],
+        false,
+    );
+    parent_task.persist_result = Some(false);
+    let response = client
+        .submit_task(Request::new(parent_task))
+        .await
+        .expect("Failed to submit fire-and-forget task");
+    assert_eq!(response.into_inner().result, "3");
+
+    // Only the completion is recorded, not the result value
+    let result = client
+        .get_result(Request::new(ResultRequest {
+            task_id: "add_unpersisted".to_string(),
+        }))
+        .await
+        .expect("Failed to get result for add_unpersisted")
+        .into_inner();
+    assert_eq!(result.status, task_response::Status::NotPersisted as i32);
+    assert!(result.result.is_empty());
+
+    // Dependents can still run after it
+    let child_task = create_task_request(
+        "add_after_unpersisted",
+        "add",
+        vec![any_i32(4)],
+        vec!["add_unpersisted".to_string()],
+        false,
+    );
+    let response = client
+        .submit_task(Request::new(child_task))
+        .await
+        .expect("Failed to submit dependent task");
+    assert_eq!(response.into_inner().result, "4");
+
+    // But its result cannot be bound into their arguments
+    let mut bound_task = create_task_request(
+        "add_bound_unpersisted",
+        "add",
+        vec![any_i32(4)],
+        vec!["add_unpersisted".to_string()],
+        false,
+    );
+    bound_task.bindings = vec![ArgBinding {
+        dep: "add_unpersisted".to_string(),
+        position: 0,
+        ..Default::default()
+    }];
+    let status = client
+        .submit_task(Request::new(bound_task))
+        .await
+        .expect_err("Expected binding to an unpersisted result to fail");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_validate_task() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let valid_task = create_task_request(
+        "validate_ok",
+        "add",
+        vec![any_i32(1), any_i32(2)],
+        vec![],
+        false,
+    );
+    let response = client
+        .validate_task(Request::new(valid_task))
+        .await
+        .expect("Failed to validate task")
+        .into_inner();
+    assert!(
+        response.valid,
+        "Unexpected problems: {:?}",
+        response.problems
+    );
+    assert!(response.problems.is_empty());
+
+    // Validation never runs the task
+    let result = client
+        .get_result(Request::new(ResultRequest {
+            task_id: "validate_ok".to_string(),
+        }))
+        .await
+        .expect("Failed to get result for validate_ok")
+        .into_inner();
+    assert_eq!(result.status, task_response::Status::Pending as i32);
+
+    // Every problem is reported, not just the first one
+    let mut invalid_task = create_task_request(
+        "validate_invalid",
+        "no_such_method",
+        vec![prost_types::Any {
+            type_url: "type.googleapis.com/unknown.Type".to_string(),
+            value: vec![],
+        }],
+        vec!["missing_parent".to_string()],
+        false,
+    );
+    invalid_task.timeout_seconds = u64::MAX;
+    invalid_task.bindings = vec![ArgBinding {
+        dep: "undeclared_parent".to_string(),
+        position: 0,
+        ..Default::default()
+    }];
+    let response = client
+        .validate_task(Request::new(invalid_task))
+        .await
+        .expect("Failed to validate task")
+        .into_inner();
+    assert!(!response.valid);
+    assert_eq!(response.problems.len(), 5, "{:?}", response.problems);
+    let problems = response.problems.join("\n");
+    assert!(problems.contains("Timeout of"));
+    assert!(problems.contains("Unsupported"));
+    assert!(problems.contains("Method not found: no_such_method"));
+    assert!(problems.contains("Dependency 'missing_parent' not found"));
+    assert!(problems.contains("Binding references 'undeclared_parent'"));
+}
+
+#[tokio::test]
+async fn test_dependency_policies() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    for task_id in ["policy_ok_1", "policy_ok_2"] {
+        let task = create_task_request(task_id, "add", vec![any_i32(1)], vec![], false);
+        client
+            .submit_task(Request::new(task))
+            .await
+            .expect("Failed to submit upstream task");
+    }
+    // Failed upstream tasks never count as completed
+    let failed_task =
+        create_task_request("policy_failed", "remove", vec![any_i32(1)], vec![], false);
+    client
+        .submit_task(Request::new(failed_task))
+        .await
+        .expect_err("Expected upstream task to fail");
+
+    let deps = vec![
+        "policy_ok_1".to_string(),
+        "policy_ok_2".to_string(),
+        "policy_failed".to_string(),
+    ];
+    let cases = [
+        (
+            "policy_all",
+            DependencyPolicy::All,
+            0,
+            Some(tonic::Code::FailedPrecondition),
+        ),
+        ("policy_any", DependencyPolicy::Any, 0, None),
+        ("policy_quorum_met", DependencyPolicy::Quorum, 2, None),
+        (
+            "policy_quorum_short",
+            DependencyPolicy::Quorum,
+            3,
+            Some(tonic::Code::FailedPrecondition),
+        ),
+        (
+            "policy_quorum_zero",
+            DependencyPolicy::Quorum,
+            0,
+            Some(tonic::Code::InvalidArgument),
+        ),
+        (
+            "policy_quorum_over",
+            DependencyPolicy::Quorum,
+            4,
+            Some(tonic::Code::InvalidArgument),
+        ),
+    ];
+
+    for (task_id, policy, quorum, expected_error) in cases {
+        let mut task = create_task_request(task_id, "add", vec![any_i32(1)], deps.clone(), false);
+        task.set_dependency_policy(policy);
+        task.dependency_quorum = quorum;
+
+        let result = client.submit_task(Request::new(task)).await;
+        match expected_error {
+            None => assert_eq!(
+                result.expect("Expected task to run").into_inner().result,
+                "1",
+                "{}",
+                task_id
+            ),
+            Some(code) => assert_eq!(
+                result.expect_err("Expected task to be rejected").code(),
+                code,
+                "{}",
+                task_id
+            ),
+        }
+    }
+
+    // ANY still needs at least one completed dependency
+    let mut none_completed = create_task_request(
+        "policy_any_none",
+        "add",
+        vec![any_i32(1)],
+        vec!["policy_failed".to_string(), "policy_missing".to_string()],
+        false,
+    );
+    none_completed.set_dependency_policy(DependencyPolicy::Any);
+    let status = client
+        .submit_task(Request::new(none_completed))
+        .await
+        .expect_err("Expected task without completed dependencies to be rejected");
+    assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+    assert!(status
+        .message()
+        .contains("0 of 2 dependencies completed, 1 required"));
+}
+
+#[tokio::test]
+async fn test_method_alias() {
+    let token_file = admin_token_file();
+    let (server, logs) = common::setup_with_logs(&[
+        "--method-alias",
+        "legacy_add=add",
+        "--admin-token-file",
+        token_file.path().to_str().expect("Non UTF-8 token path"),
+    ])
+    .await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let task = create_task_request(
+        "alias_add",
+        "legacy_add",
+        vec![any_i32(2), any_i32(3)],
+        vec![],
+        false,
+    );
+
+    // A dry run resolves the alias without counting it as a use
+    let validation = client
+        .validate_task(Request::new(task.clone()))
+        .await
+        .expect("Failed to validate aliased task")
+        .into_inner();
+    assert!(validation.valid, "{:?}", validation.problems);
+    let stats = get_stats(&server.address()).await;
+    assert_eq!(stats.alias_uses.get("legacy_add"), Some(&0));
+
+    let response = client
+        .submit_task(Request::new(task))
+        .await
+        .expect("Failed to submit aliased task")
+        .into_inner();
+    assert_eq!(response.result, "5");
+
+    // Give the non-blocking log writer time to flush
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    assert!(
+        logs.contains("uses deprecated method 'legacy_add', running 'add' instead"),
+        "Expected a deprecation warning for the alias"
+    );
+    let stats = get_stats(&server.address()).await;
+    assert_eq!(stats.alias_uses.get("legacy_add"), Some(&1));
+}
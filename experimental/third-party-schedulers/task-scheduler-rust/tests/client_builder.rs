@@ -0,0 +1,103 @@
+mod common;
+
+use std::collections::HashMap;
+use std::time::Duration;
+use task_client::taskscheduler::arg_binding::ValueType;
+use task_client::taskscheduler::task_response;
+use task_client::{ArgValue, TaskClient};
+
+#[tokio::test]
+async fn test_client_submit() {
+    let server = common::setup().await;
+    let client = TaskClient::connect(server.address())
+        .await
+        .expect("Failed to connect client");
+
+    let sum = client
+        .submit("client_add")
+        .method("add")
+        .arg(1)
+        .args([2, 3])
+        .send()
+        .await
+        .expect("Failed to submit task");
+    assert_eq!(sum, "6");
+
+    // Collections are encoded the same way the server decodes them
+    let mut map = HashMap::new();
+    map.insert("key".to_string(), ArgValue::from("value"));
+    let description = client
+        .submit("client_collection")
+        .method("process_collection")
+        .arg(vec![ArgValue::from(1), ArgValue::from("two")])
+        .arg(map)
+        .send()
+        .await
+        .expect("Failed to submit task");
+    assert_eq!(description, "Array: 2 items, Map: 1 items");
+
+    // Binding a dependency result declares the dependency as well
+    let difference = client
+        .submit("client_remove")
+        .method("remove")
+        .arg(1)
+        .bind_as("client_add", 0, ValueType::Int32)
+        .send()
+        .await
+        .expect("Failed to submit dependent task");
+    assert_eq!(difference, "5");
+
+    let status = client
+        .submit("client_missing")
+        .method("no_such_method")
+        .send()
+        .await
+        .expect_err("Expected an unknown method to fail");
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    let problems = client
+        .submit("client_invalid")
+        .method("no_such_method")
+        .dep("client_never_submitted")
+        .validate()
+        .await
+        .expect("Failed to validate task");
+    assert_eq!(problems.len(), 2, "{:?}", problems);
+}
+
+#[tokio::test]
+async fn test_client_wait_for_result() {
+    let server = common::setup().await;
+    let client = TaskClient::connect(server.address())
+        .await
+        .expect("Failed to connect client");
+
+    // Another client submits a slow task, this one waits for its result
+    let submitter = client.clone();
+    let submission = tokio::spawn(async move {
+        submitter
+            .submit("client_slow")
+            .method("sleep")
+            .arg(300)
+            .is_async(true)
+            .send()
+            .await
+    });
+
+    let result = client
+        .wait_for_result("client_slow", Duration::from_secs(5))
+        .await
+        .expect("Failed to wait for result");
+    assert_eq!(result.status, task_response::Status::Success as i32);
+    assert_eq!(result.result, "Slept 300 ms");
+    submission
+        .await
+        .expect("Submission task panicked")
+        .expect("Failed to submit task");
+
+    let status = client
+        .wait_for_result("client_never_submitted", Duration::from_millis(300))
+        .await
+        .expect_err("Expected waiting for an unknown task to time out");
+    assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+}
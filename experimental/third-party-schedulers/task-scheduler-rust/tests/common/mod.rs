@@ -1,5 +1,6 @@
+use std::io::{BufRead, BufReader};
 use std::process::{Child, Command, Stdio};
-use std::sync::{Mutex, Once};
+use std::sync::{Arc, Mutex, Once};
 use std::time::Duration;
 
 pub mod utils;
@@ -25,6 +26,28 @@ impl TestServer {
     pub fn address(&self) -> String {
         format!("http://127.0.0.1:{}", self.port)
     }
+
+    #[allow(dead_code)]
+    pub fn pid(&self) -> u32 {
+        self.process.id()
+    }
+
+    /// Wait up to `timeout` for the server process to exit on its own
+    #[allow(dead_code)]
+    pub fn wait_for_exit(&mut self, timeout: Duration) -> Option<std::process::ExitStatus> {
+        let start = std::time::Instant::now();
+        while start.elapsed() < timeout {
+            if let Some(status) = self
+                .process
+                .try_wait()
+                .expect("Failed to poll server process")
+            {
+                return Some(status);
+            }
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        None
+    }
 }
 
 impl Drop for TestServer {
@@ -60,28 +83,59 @@ impl Drop for TestServer {
     }
 }
 
-/// Set up test server with automatically assigned port
-#[allow(clippy::zombie_processes)]
-pub async fn setup() -> TestServer {
-    // Ensure binary is built only once
+/// Build the server binary, only once per test binary
+fn ensure_built() {
     INIT.call_once(|| {
         println!("Ensuring test server binary is built...");
         assert!(
             Command::new("cargo")
-                .args(["build", "--bin", "task-scheduler"])
+                .args([
+                    "build",
+                    "--bin",
+                    "task-scheduler",
+                    "--features",
+                    "test-tasks"
+                ])
                 .status()
                 .expect("Failed to build server binary")
                 .success(),
             "Failed to build the server binary"
         );
     });
+}
+
+/// Lines the test server wrote to its standard output
+#[derive(Clone, Default)]
+#[allow(dead_code)]
+pub struct ServerLogs(Arc<Mutex<Vec<String>>>);
+
+#[allow(dead_code)]
+impl ServerLogs {
+    pub fn contains(&self, pattern: &str) -> bool {
+        self.0
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|line| line.contains(pattern))
+    }
+}
+
+/// Start the server on `port` with `args` and wait for it to come up. Its standard output is
+/// only kept in the returned logs when `capture_logs` is set.
+#[allow(clippy::zombie_processes)]
+async fn spawn_server(port: u16, args: &[&str], capture_logs: bool) -> (TestServer, ServerLogs) {
+    ensure_built();
 
-    let port = get_next_port();
     println!("Starting test server process on port {}...", port);
-    let process = Command::new("target/debug/task-scheduler")
+    let mut process = Command::new("target/debug/task-scheduler")
         .arg("--addr")
         .arg(format!("127.0.0.1:{}", port))
-        .stdout(Stdio::inherit())
+        .args(args)
+        .stdout(if capture_logs {
+            Stdio::piped()
+        } else {
+            Stdio::inherit()
+        })
         .stderr(Stdio::inherit())
         .spawn()
         .expect("Failed to start server process");
@@ -91,53 +145,48 @@ pub async fn setup() -> TestServer {
         port
     );
 
+    // Forward the output while keeping a copy for assertions
+    let logs = ServerLogs::default();
+    if let Some(stdout) = process.stdout.take() {
+        let lines = logs.0.clone();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                println!("{}", line);
+                lines.lock().unwrap().push(line);
+            }
+        });
+    }
+
     // Wait for server to start
     tokio::time::sleep(Duration::from_millis(700)).await;
 
-    TestServer { process, port }
+    (TestServer { process, port }, logs)
+}
+
+/// Set up test server with automatically assigned port
+pub async fn setup() -> TestServer {
+    spawn_server(get_next_port(), &[], false).await.0
 }
 
 /// Set up test server with custom options
-#[allow(clippy::zombie_processes)]
 #[allow(dead_code)]
 pub async fn setup_with_options(port: Option<u16>, library_dir: Option<&str>) -> TestServer {
-    // Ensure binary is built only once
-    INIT.call_once(|| {
-        println!("Ensuring test server binary is built...");
-        assert!(
-            Command::new("cargo")
-                .args(["build", "--bin", "task-scheduler"])
-                .status()
-                .expect("Failed to build server binary")
-                .success(),
-            "Failed to build the server binary"
-        );
-    });
-
     let port = port.unwrap_or_else(get_next_port);
-    println!("Starting test server process on port {}...", port);
-    let mut command = Command::new("target/debug/task-scheduler");
-    command.arg("--addr").arg(format!("127.0.0.1:{}", port));
-
-    // Add library directory option if provided
-    if let Some(dir) = library_dir {
-        command.arg("--library-dir").arg(dir);
-    }
-
-    let process = command
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .expect("Failed to start server process");
-
-    println!(
-        "Test server process started (PID: {}, Port: {}).",
-        process.id(),
-        port
-    );
+    let args = match library_dir {
+        Some(dir) => vec!["--library-dir", dir],
+        None => vec![],
+    };
+    spawn_server(port, &args, false).await.0
+}
 
-    // Wait for server to start
-    tokio::time::sleep(Duration::from_millis(700)).await;
+/// Set up test server with additional command line arguments
+#[allow(dead_code)]
+pub async fn setup_with_args(extra_args: &[&str]) -> TestServer {
+    spawn_server(get_next_port(), extra_args, false).await.0
+}
 
-    TestServer { process, port }
+/// Set up test server with additional command line arguments, capturing its standard output
+#[allow(dead_code)]
+pub async fn setup_with_logs(extra_args: &[&str]) -> (TestServer, ServerLogs) {
+    spawn_server(get_next_port(), extra_args, true).await
 }
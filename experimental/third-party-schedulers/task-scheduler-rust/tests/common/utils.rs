@@ -1,11 +1,19 @@
 use prost::Message;
 use prost_types::Any;
 use std::collections::HashMap;
+use std::io::Write;
 use task_scheduler::models::wrappers::{BoolValue, BytesValue, Int32Value, StringValue};
 use task_scheduler::tasks::taskscheduler::{
-    task_scheduler_client::TaskSchedulerClient, ListValue, MapValue, TaskRequest,
+    task_scheduler_admin_client::TaskSchedulerAdminClient,
+    task_scheduler_client::TaskSchedulerClient, ListValue, MapValue, StatsRequest, StatsResponse,
+    TaskRequest,
 };
 use tonic::transport::Channel;
+use tonic::Request;
+
+/// Token the admin helpers authenticate with
+#[allow(dead_code)]
+pub const ADMIN_TOKEN: &str = "test-admin-secret";
 
 /// Connect to the server
 pub async fn connect_to_server(server_address: &str) -> TaskSchedulerClient<Channel> {
@@ -18,6 +26,34 @@ pub async fn connect_to_server(server_address: &str) -> TaskSchedulerClient<Chan
     TaskSchedulerClient::new(channel)
 }
 
+/// Write `ADMIN_TOKEN` to a file to pass as `--admin-token-file`, removed once dropped
+#[allow(dead_code)]
+pub fn admin_token_file() -> tempfile::NamedTempFile {
+    let mut token_file = tempfile::NamedTempFile::new().expect("Failed to create token file");
+    writeln!(token_file, "{}", ADMIN_TOKEN).expect("Failed to write token file");
+    token_file
+}
+
+/// Fetch the scheduler stats through the admin service
+#[allow(dead_code)]
+pub async fn get_stats(server_address: &str) -> StatsResponse {
+    let mut client = TaskSchedulerAdminClient::connect(server_address.to_string())
+        .await
+        .expect("Failed to connect admin client");
+    let mut request = Request::new(StatsRequest {});
+    request.metadata_mut().insert(
+        "authorization",
+        format!("Bearer {}", ADMIN_TOKEN)
+            .parse()
+            .expect("Invalid token metadata"),
+    );
+    client
+        .get_stats(request)
+        .await
+        .expect("Failed to get stats")
+        .into_inner()
+}
+
 /// Convert i32 to Any message
 pub fn any_i32(val: i32) -> Any {
     Any {
@@ -97,5 +133,6 @@ pub fn create_task_request(
         args,
         deps,
         is_async,
+        ..Default::default()
     }
 }
@@ -1,7 +1,14 @@
 mod common;
 
 use common::utils::{any_i32, any_string, connect_to_server, create_task_request};
+use ed25519_dalek::{Signer, SigningKey};
 use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::Duration;
+use task_scheduler::error::TaskError;
+use task_scheduler::tasks::dynamic::{init_dynamic_loader, DynamicTaskLoader};
+use task_scheduler::tasks::{load_plugin, unload_plugin, REGISTRY};
 use tonic::Request;
 
 #[tokio::test]
@@ -177,3 +184,198 @@ async fn test_plugin_unload() {
     let task_response = response.into_inner();
     assert_eq!(task_response.result, "50");
 }
+
+#[tokio::test]
+async fn test_plugin_abi_mismatch_rejected() {
+    assert!(
+        Command::new("cargo")
+            .args([
+                "build",
+                "--manifest-path",
+                "examples/abi_mismatch_plugin/Cargo.toml",
+            ])
+            .status()
+            .expect("Failed to build ABI mismatch plugin")
+            .success(),
+        "Failed to build the ABI mismatch plugin"
+    );
+
+    let abi_dir = "./abi_mismatch_libs";
+    let _ = fs::remove_dir_all(abi_dir);
+    let _ = fs::create_dir_all(abi_dir);
+
+    fs::copy(
+        "examples/abi_mismatch_plugin/target/debug/libabi_mismatch_plugin.so",
+        format!("{}/libabi_mismatch.so", abi_dir),
+    )
+    .expect("Failed to copy ABI mismatch plugin");
+    fs::copy(
+        "libraries/libplugin_example.so",
+        format!("{}/libplugin_example.so", abi_dir),
+    )
+    .expect("Failed to copy plugin");
+
+    let server = common::setup_with_options(None, Some(abi_dir)).await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // The incompatible plugin is refused, so its task is never registered
+    let task_request = create_task_request(
+        "abi_mismatch_test",
+        "abi_mismatch::noop",
+        Vec::new(),
+        Vec::new(),
+        false,
+    );
+    let status = client
+        .submit_task(Request::new(task_request))
+        .await
+        .expect_err("Task from an incompatible plugin should not be available");
+    assert_eq!(status.code(), tonic::Code::NotFound);
+
+    // Compatible plugins in the same directory still load
+    let task_request = create_task_request(
+        "abi_compatible_test",
+        "plugin_example::multiply",
+        vec![any_i32(6), any_i32(7)],
+        Vec::new(),
+        false,
+    );
+    let response = client
+        .submit_task(Request::new(task_request))
+        .await
+        .expect("RPC failed");
+    assert_eq!(response.into_inner().result, "42");
+
+    drop(client);
+    drop(server);
+    let _ = fs::remove_dir_all(abi_dir);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_plugin_unload_refused_while_in_use() {
+    init_dynamic_loader(PathBuf::from("./libraries"));
+    load_plugin("plugin_example").expect("Failed to load plugin");
+
+    // delayed_echo sleeps for 500ms, keeping the plugin busy
+    let task_request = create_task_request(
+        "plugin_in_flight",
+        "plugin_example::delayed_echo",
+        vec![any_string("busy")],
+        Vec::new(),
+        true,
+    );
+    let in_flight = tokio::spawn(async move { REGISTRY.execute_task(&task_request).await });
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let err = unload_plugin("plugin_example")
+        .expect_err("Unload should be refused while a plugin task is running");
+    assert!(matches!(err, TaskError::PluginInUse(_)));
+
+    let result = in_flight
+        .await
+        .expect("Task panicked")
+        .expect("Task failed");
+    assert!(result.contains("busy"));
+
+    // Once the task has finished the plugin can be unloaded
+    let unloaded = unload_plugin("plugin_example").expect("Unload should succeed once idle");
+    assert!(unloaded.contains(&"plugin_example::delayed_echo".to_string()));
+}
+
+#[tokio::test]
+async fn test_signed_plugin_verification() {
+    let signed_dir = "./signed_plugin_libs";
+    let _ = fs::remove_dir_all(signed_dir);
+    let _ = fs::create_dir_all(signed_dir);
+
+    let library_bytes =
+        fs::read("libraries/libplugin_example.so").expect("Failed to read plugin library");
+    let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+    let signature_hex: String = signing_key
+        .sign(&library_bytes)
+        .to_bytes()
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+
+    // Correctly signed plugin
+    fs::write(
+        format!("{}/libsigned_plugin.so", signed_dir),
+        &library_bytes,
+    )
+    .expect("Failed to write signed plugin");
+    fs::write(
+        format!("{}/libsigned_plugin.so.sig", signed_dir),
+        &signature_hex,
+    )
+    .expect("Failed to write signature");
+
+    // Tampered plugin carrying the signature of the original bytes
+    let mut tampered_bytes = library_bytes.clone();
+    tampered_bytes.push(0);
+    fs::write(
+        format!("{}/libtampered_plugin.so", signed_dir),
+        &tampered_bytes,
+    )
+    .expect("Failed to write tampered plugin");
+    fs::write(
+        format!("{}/libtampered_plugin.so.sig", signed_dir),
+        &signature_hex,
+    )
+    .expect("Failed to write signature");
+
+    // Plugin without a signature file
+    fs::write(
+        format!("{}/libunsigned_plugin.so", signed_dir),
+        &library_bytes,
+    )
+    .expect("Failed to write unsigned plugin");
+
+    let loader =
+        DynamicTaskLoader::new(signed_dir).with_trusted_keys(vec![signing_key.verifying_key()]);
+
+    let tasks = loader
+        .load_plugin("signed_plugin")
+        .expect("A validly signed plugin should load");
+    assert!(tasks.contains(&"signed_plugin::multiply".to_string()));
+
+    let err = loader
+        .load_plugin("tampered_plugin")
+        .expect_err("A tampered plugin should be rejected");
+    assert!(err.to_string().contains("does not match any trusted key"));
+
+    let err = loader
+        .load_plugin("unsigned_plugin")
+        .expect_err("An unsigned plugin should be rejected");
+    assert!(err.to_string().contains("signature file"));
+
+    // A key that did not sign the plugin is not trusted
+    let untrusted_loader = DynamicTaskLoader::new(signed_dir)
+        .with_trusted_keys(vec![SigningKey::from_bytes(&[9u8; 32]).verifying_key()]);
+    assert!(untrusted_loader.load_plugin("signed_plugin").is_err());
+
+    let _ = loader.unload_plugin("signed_plugin");
+
+    // Verified copies can be written to another directory, and are removed once loaded
+    let copy_dir = "./signed_plugin_copies";
+    let _ = fs::remove_dir_all(copy_dir);
+    fs::create_dir_all(copy_dir).expect("Failed to create copy directory");
+    let copy_dir_loader = DynamicTaskLoader::new(signed_dir)
+        .with_trusted_keys(vec![signing_key.verifying_key()])
+        .with_verified_copy_dir(PathBuf::from(copy_dir));
+    let tasks = copy_dir_loader
+        .load_plugin("signed_plugin")
+        .expect("A validly signed plugin should load from a custom copy directory");
+    assert!(tasks.contains(&"signed_plugin::multiply".to_string()));
+    assert_eq!(
+        fs::read_dir(copy_dir)
+            .expect("Failed to read copy directory")
+            .count(),
+        0,
+        "The verified copy should be removed once loaded"
+    );
+
+    let _ = copy_dir_loader.unload_plugin("signed_plugin");
+    let _ = fs::remove_dir_all(copy_dir);
+    let _ = fs::remove_dir_all(signed_dir);
+}
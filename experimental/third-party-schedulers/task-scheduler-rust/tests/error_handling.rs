@@ -1,6 +1,10 @@
 mod common;
 
-use common::utils::{any_i32, connect_to_server, create_task_request};
+use common::utils::{
+    admin_token_file, any_bool, any_bytes, any_i32, any_string, connect_to_server,
+    create_task_request, get_stats,
+};
+use task_scheduler::tasks::taskscheduler::stats_response::CircuitState;
 use tonic::Request;
 
 #[tokio::test]
@@ -49,3 +53,680 @@ async fn test_error_handling() {
         panic!("Expected Err status but got Ok");
     }
 }
+
+#[tokio::test]
+async fn test_task_timeout() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // A 5s sleep with a 1s timeout must fail promptly
+    let mut sleep_task =
+        create_task_request("sleep_timeout", "sleep", vec![any_i32(5000)], vec![], true);
+    sleep_task.timeout_seconds = 1;
+
+    let start = std::time::Instant::now();
+    let status = client
+        .submit_task(Request::new(sleep_task))
+        .await
+        .expect_err("Expected the task to time out");
+    assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    assert!(status.message().contains("Task timed out"));
+    assert!(
+        start.elapsed() < std::time::Duration::from_secs(3),
+        "Timeout should fire well before the task finishes"
+    );
+
+    // A short sleep within its timeout succeeds
+    let mut quick_task =
+        create_task_request("sleep_quick", "sleep", vec![any_i32(100)], vec![], true);
+    quick_task.timeout_seconds = 1;
+    let response = client
+        .submit_task(Request::new(quick_task))
+        .await
+        .expect("Failed to submit task");
+    assert_eq!(response.into_inner().result, "Slept 100 ms");
+
+    // Timeouts above the server maximum are rejected
+    let mut oversized_task =
+        create_task_request("sleep_oversized", "sleep", vec![any_i32(0)], vec![], true);
+    oversized_task.timeout_seconds = u64::MAX;
+    let status = client
+        .submit_task(Request::new(oversized_task))
+        .await
+        .expect_err("Expected an oversized timeout to be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_sync_task_timeout() {
+    let token_file = admin_token_file();
+    let server = common::setup_with_args(&[
+        "--admin-token-file",
+        token_file.path().to_str().expect("Non UTF-8 token path"),
+    ])
+    .await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // A blocking 5s sleep with a 1s timeout is abandoned instead of waited for, and not retried
+    // since every attempt would abandon another thread
+    let mut blocking_task = create_task_request(
+        "sleep_blocking_timeout",
+        "sleep_blocking",
+        vec![any_i32(5000)],
+        vec![],
+        false,
+    );
+    blocking_task.timeout_seconds = 1;
+    blocking_task.max_retries = 3;
+
+    let start = std::time::Instant::now();
+    let status = client
+        .submit_task(Request::new(blocking_task))
+        .await
+        .expect_err("Expected the sync task to time out");
+    assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    assert!(
+        start.elapsed() < std::time::Duration::from_secs(3),
+        "Timeout should fire well before the sync task finishes"
+    );
+    let stats = get_stats(&server.address()).await;
+    assert_eq!(stats.abandoned_sync_threads, 1);
+    assert_eq!(stats.live_sync_threads, 1);
+
+    // The server keeps serving while the abandoned thread is still sleeping
+    let mut quick_task = create_task_request(
+        "sleep_blocking_quick",
+        "sleep_blocking",
+        vec![any_i32(100)],
+        vec![],
+        false,
+    );
+    quick_task.timeout_seconds = 1;
+    let response = client
+        .submit_task(Request::new(quick_task))
+        .await
+        .expect("Failed to submit task");
+    assert_eq!(response.into_inner().result, "Slept 100 ms");
+}
+
+#[tokio::test]
+async fn test_sync_thread_limit() {
+    let token_file = admin_token_file();
+    let server = common::setup_with_args(&[
+        "--max-sync-threads",
+        "1",
+        "--admin-token-file",
+        token_file.path().to_str().expect("Non UTF-8 token path"),
+    ])
+    .await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let blocking_task = |task_id: &str, millis: i32| {
+        let mut task = create_task_request(
+            task_id,
+            "sleep_blocking",
+            vec![any_i32(millis)],
+            vec![],
+            false,
+        );
+        task.timeout_seconds = 1;
+        task
+    };
+
+    // The abandoned thread keeps the only slot until it returns
+    let status = client
+        .submit_task(Request::new(blocking_task("thread_limit_hung", 2500)))
+        .await
+        .expect_err("Expected the sync task to time out");
+    assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+
+    let status = client
+        .submit_task(Request::new(blocking_task("thread_limit_rejected", 0)))
+        .await
+        .expect_err("Expected the thread limit to reject the task");
+    assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+
+    // Once the abandoned thread returns, sync tasks run again
+    tokio::time::sleep(std::time::Duration::from_millis(2000)).await;
+    let response = client
+        .submit_task(Request::new(blocking_task("thread_limit_after", 0)))
+        .await
+        .expect("Expected the task to run once the thread returned");
+    assert_eq!(response.into_inner().result, "Slept 0 ms");
+    let stats = get_stats(&server.address()).await;
+    assert_eq!(stats.abandoned_sync_threads, 1);
+    assert_eq!(stats.live_sync_threads, 0);
+}
+
+#[tokio::test]
+async fn test_client_deadline() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // The client deadline applies even though the task's own timeout is much longer
+    for (task_id, method, is_async) in [
+        ("deadline_async", "sleep", true),
+        ("deadline_sync", "sleep_blocking", false),
+    ] {
+        let mut request = Request::new(create_task_request(
+            task_id,
+            method,
+            vec![any_i32(5000)],
+            vec![],
+            is_async,
+        ));
+        request.set_timeout(std::time::Duration::from_millis(500));
+
+        let start = std::time::Instant::now();
+        let status = client
+            .submit_task(request)
+            .await
+            .expect_err("Expected the client deadline to be exceeded");
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded, "{}", task_id);
+        assert!(status.message().contains("client deadline"));
+        assert!(
+            start.elapsed() < std::time::Duration::from_secs(2),
+            "Deadline should fire well before the task finishes"
+        );
+    }
+
+    // Tasks finishing within the deadline are unaffected
+    let mut request = Request::new(create_task_request(
+        "deadline_quick",
+        "sleep",
+        vec![any_i32(100)],
+        vec![],
+        true,
+    ));
+    request.set_timeout(std::time::Duration::from_secs(2));
+    let response = client
+        .submit_task(request)
+        .await
+        .expect("Failed to submit task");
+    assert_eq!(response.into_inner().result, "Slept 100 ms");
+}
+
+#[tokio::test]
+async fn test_client_deadline_close_to_task_duration() {
+    let server = common::setup_with_args(&["--deadline-margin-percent", "20"]).await;
+    let client = connect_to_server(&server.address()).await;
+
+    // Tasks lasting exactly as long as the deadline, submitted at once to put the server under
+    // load, still fail with the scheduler's error rather than tonic cancelling the call
+    let mut handles = Vec::new();
+    for i in 0..8 {
+        let mut client = client.clone();
+        let (method, is_async) = if i % 2 == 0 {
+            ("sleep", true)
+        } else {
+            ("sleep_blocking", false)
+        };
+        handles.push(tokio::spawn(async move {
+            let mut request = Request::new(create_task_request(
+                &format!("deadline_close_{}", i),
+                method,
+                vec![any_i32(500)],
+                vec![],
+                is_async,
+            ));
+            request.set_timeout(std::time::Duration::from_millis(500));
+            client.submit_task(request).await
+        }));
+    }
+
+    for handle in handles {
+        let status = handle
+            .await
+            .expect("Submission panicked")
+            .expect_err("Expected the client deadline to be exceeded");
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+        assert!(
+            status.message().contains("client deadline"),
+            "Expected the scheduler's error, got: {}",
+            status.message()
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_task_retries() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // max_retries = 0 fails on the first error
+    let no_retry_task = create_task_request(
+        "flaky_no_retry",
+        "flaky",
+        vec![any_string("no_retry"), any_i32(1)],
+        vec![],
+        false,
+    );
+    let status = client
+        .submit_task(Request::new(no_retry_task))
+        .await
+        .expect_err("Expected the task to fail without retrying");
+    assert_eq!(status.code(), tonic::Code::Internal);
+    assert!(status.message().contains("Flaky failure 1 of 1"));
+
+    // max_retries = 5 survives five failures
+    let mut retried_task = create_task_request(
+        "flaky_retried",
+        "flaky",
+        vec![any_string("retried"), any_i32(5)],
+        vec![],
+        false,
+    );
+    retried_task.max_retries = 5;
+    let response = client
+        .submit_task(Request::new(retried_task))
+        .await
+        .expect("Expected the task to succeed after retrying");
+    assert_eq!(response.into_inner().result, "Succeeded after 5 failures");
+
+    // max_retries = 5 gives up after the sixth failure
+    let mut exhausted_task = create_task_request(
+        "flaky_exhausted",
+        "flaky",
+        vec![any_string("exhausted"), any_i32(6)],
+        vec![],
+        false,
+    );
+    exhausted_task.max_retries = 5;
+    let status = client
+        .submit_task(Request::new(exhausted_task))
+        .await
+        .expect_err("Expected the task to exhaust its retries");
+    assert!(status.message().contains("Flaky failure 6 of 6"));
+
+    // Retry counts above the server maximum are rejected
+    let mut oversized_task = create_task_request(
+        "flaky_oversized",
+        "flaky",
+        vec![any_string("oversized"), any_i32(0)],
+        vec![],
+        false,
+    );
+    oversized_task.max_retries = u32::MAX;
+    let status = client
+        .submit_task(Request::new(oversized_task))
+        .await
+        .expect_err("Expected oversized max_retries to be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+}
+
+#[tokio::test]
+async fn test_retry_budget() {
+    let token_file = admin_token_file();
+    let server = common::setup_with_args(&[
+        "--retry-budget",
+        "3",
+        "--retry-budget-refill-per-sec",
+        "0",
+        "--admin-token-file",
+        token_file.path().to_str().expect("Non UTF-8 token path"),
+    ])
+    .await;
+    let mut client = connect_to_server(&server.address()).await;
+    assert_eq!(
+        get_stats(&server.address()).await.retry_budget_remaining,
+        Some(3)
+    );
+
+    let flaky_task = |task_id: &str, failures: i32| {
+        let mut task = create_task_request(
+            task_id,
+            "flaky",
+            vec![any_string(task_id), any_i32(failures)],
+            vec![],
+            false,
+        );
+        task.max_retries = 5;
+        task
+    };
+
+    // Two retries are spent from the budget of three
+    let response = client
+        .submit_task(Request::new(flaky_task("budget_first", 2)))
+        .await
+        .expect("Expected the task to succeed after retrying");
+    assert_eq!(response.into_inner().result, "Succeeded after 2 failures");
+    assert_eq!(
+        get_stats(&server.address()).await.retry_budget_remaining,
+        Some(1)
+    );
+
+    // The last retry is spent, the second failure is not retried anymore
+    let status = client
+        .submit_task(Request::new(flaky_task("budget_second", 2)))
+        .await
+        .expect_err("Expected the task to fail once the budget is exhausted");
+    assert_eq!(status.code(), tonic::Code::Internal);
+    assert!(status.message().contains("Flaky failure 2 of 2"));
+
+    // With the budget exhausted, failures are not retried at all
+    let status = client
+        .submit_task(Request::new(flaky_task("budget_third", 1)))
+        .await
+        .expect_err("Expected the task to fail without retrying");
+    assert_eq!(status.code(), tonic::Code::Internal);
+    assert!(status.message().contains("Flaky failure 1 of 1"));
+    assert_eq!(
+        get_stats(&server.address()).await.retry_budget_remaining,
+        Some(0)
+    );
+}
+
+#[tokio::test]
+async fn test_circuit_breaker() {
+    let token_file = admin_token_file();
+    let server = common::setup_with_args(&[
+        "--circuit-breaker-threshold",
+        "3",
+        "--circuit-breaker-open-secs",
+        "1",
+        "--admin-token-file",
+        token_file.path().to_str().expect("Non UTF-8 token path"),
+    ])
+    .await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let failing_task = |task_id: &str| {
+        create_task_request(
+            task_id,
+            "flaky",
+            vec![any_string("always_failing"), any_i32(i32::MAX)],
+            vec![],
+            false,
+        )
+    };
+
+    // Three consecutive failures open the circuit
+    for attempt in 0..3 {
+        let status = client
+            .submit_task(Request::new(failing_task(&format!("breaker_{}", attempt))))
+            .await
+            .expect_err("Expected the task to fail");
+        assert_eq!(status.code(), tonic::Code::Internal);
+    }
+    let stats = get_stats(&server.address()).await;
+    assert_eq!(
+        stats.circuit_states.get("flaky"),
+        Some(&(CircuitState::Open as i32))
+    );
+
+    // While open, even a task that would succeed is rejected
+    let healthy_task = create_task_request(
+        "breaker_rejected",
+        "flaky",
+        vec![any_string("healthy"), any_i32(0)],
+        vec![],
+        false,
+    );
+    let status = client
+        .submit_task(Request::new(healthy_task))
+        .await
+        .expect_err("Expected the open circuit to reject the task");
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+
+    // After the open duration a successful probe closes the circuit
+    tokio::time::sleep(std::time::Duration::from_millis(1200)).await;
+    let probe_task = create_task_request(
+        "breaker_probe",
+        "flaky",
+        vec![any_string("healthy"), any_i32(0)],
+        vec![],
+        false,
+    );
+    let response = client
+        .submit_task(Request::new(probe_task))
+        .await
+        .expect("Expected the probe to be admitted");
+    assert_eq!(response.into_inner().result, "Succeeded after 0 failures");
+    let stats = get_stats(&server.address()).await;
+    assert!(
+        !stats.circuit_states.contains_key("flaky"),
+        "A closed circuit without failures should not be reported"
+    );
+
+    // Closed again, failures are reported normally instead of being rejected
+    let status = client
+        .submit_task(Request::new(failing_task("breaker_closed")))
+        .await
+        .expect_err("Expected the task to fail");
+    assert_eq!(status.code(), tonic::Code::Internal);
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_disabled_by_default() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // Without --circuit-breaker-threshold, repeated failures are never turned into rejections
+    for attempt in 0..8 {
+        let task = create_task_request(
+            &format!("breaker_default_{}", attempt),
+            "flaky",
+            vec![any_string("breaker_default"), any_i32(i32::MAX)],
+            vec![],
+            false,
+        );
+        let status = client
+            .submit_task(Request::new(task))
+            .await
+            .expect_err("Expected the task to fail");
+        assert_eq!(status.code(), tonic::Code::Internal);
+    }
+}
+
+#[tokio::test]
+async fn test_circuit_breaker_cancelled_probe() {
+    let server = common::setup_with_args(&[
+        "--circuit-breaker-threshold",
+        "2",
+        "--circuit-breaker-open-secs",
+        "1",
+    ])
+    .await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let sleep_task = |task_id: &str, millis: i32| {
+        let mut task = create_task_request(task_id, "sleep", vec![any_i32(millis)], vec![], true);
+        task.timeout_seconds = 1;
+        task
+    };
+
+    // Two timeouts open the circuit
+    for attempt in 0..2 {
+        let status = client
+            .submit_task(Request::new(sleep_task(
+                &format!("cancelled_probe_open_{}", attempt),
+                1500,
+            )))
+            .await
+            .expect_err("Expected the task to time out");
+        assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    }
+
+    // The probe is cancelled by the client deadline before it records a result
+    tokio::time::sleep(std::time::Duration::from_millis(1200)).await;
+    let mut request = Request::new(sleep_task("cancelled_probe", 800));
+    request.set_timeout(std::time::Duration::from_millis(300));
+    let status = client
+        .submit_task(request)
+        .await
+        .expect_err("Expected the client deadline to be exceeded");
+    assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    assert!(status.message().contains("client deadline"));
+
+    // The cancelled probe counted as a failure, so a later probe is admitted
+    tokio::time::sleep(std::time::Duration::from_millis(1200)).await;
+    let response = client
+        .submit_task(Request::new(sleep_task("cancelled_probe_after", 0)))
+        .await
+        .expect("Expected a later probe to be admitted");
+    assert_eq!(response.into_inner().result, "Slept 0 ms");
+}
+
+#[tokio::test]
+async fn test_permanent_errors_not_retried() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // A permanent error fails on the first attempt even though a retry would succeed
+    let mut permanent_task = create_task_request(
+        "flaky_permanent",
+        "flaky",
+        vec![any_string("permanent"), any_i32(1), any_bool(true)],
+        vec![],
+        false,
+    );
+    permanent_task.max_retries = 5;
+    let status = client
+        .submit_task(Request::new(permanent_task))
+        .await
+        .expect_err("Expected the permanent error not to be retried");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status.message().contains("Flaky failure 1 of 1"));
+
+    // The same failure as a transient error is retried
+    let mut transient_task = create_task_request(
+        "flaky_transient",
+        "flaky",
+        vec![any_string("transient"), any_i32(1), any_bool(false)],
+        vec![],
+        false,
+    );
+    transient_task.max_retries = 5;
+    let response = client
+        .submit_task(Request::new(transient_task))
+        .await
+        .expect("Expected the transient error to be retried");
+    assert_eq!(response.into_inner().result, "Succeeded after 1 failures");
+}
+
+#[tokio::test]
+async fn test_oversized_arguments_rejected() {
+    let server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // 2 MiB of bytes exceeds the default 1 MiB argument limit
+    let oversized_task = create_task_request(
+        "oversized_args",
+        "echo_bytes",
+        vec![any_bytes(&vec![b'a'; 2 * 1024 * 1024])],
+        vec![],
+        false,
+    );
+    let status = client
+        .submit_task(Request::new(oversized_task))
+        .await
+        .expect_err("Expected oversized arguments to be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status.message().contains("exceeding the limit"));
+
+    // The encoded Any size counts, so many small arguments add up as well
+    let many_args_task = create_task_request(
+        "many_args",
+        "echo_string",
+        (0..40)
+            .map(|_| any_string(&"a".repeat(32 * 1024)))
+            .collect(),
+        vec![],
+        false,
+    );
+    let status = client
+        .submit_task(Request::new(many_args_task))
+        .await
+        .expect_err("Expected the combined arguments to be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+
+    // Arguments within the limit are accepted
+    let small_task = create_task_request(
+        "small_args",
+        "echo_bytes",
+        vec![any_bytes(b"hello")],
+        vec![],
+        false,
+    );
+    let response = client
+        .submit_task(Request::new(small_task))
+        .await
+        .expect("Failed to submit task");
+    assert_eq!(response.into_inner().result, "hello");
+}
+
+#[tokio::test]
+async fn test_custom_max_args_bytes() {
+    let server = common::setup_with_args(&["--max-args-bytes", "1024"]).await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    // 2 KiB is well within the default limit but exceeds the configured one
+    let oversized_task = create_task_request(
+        "custom_limit_oversized",
+        "echo_bytes",
+        vec![any_bytes(&[b'a'; 2048])],
+        vec![],
+        false,
+    );
+    let status = client
+        .submit_task(Request::new(oversized_task))
+        .await
+        .expect_err("Expected arguments over the configured limit to be rejected");
+    assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    assert!(status
+        .message()
+        .contains("exceeding the limit of 1024 bytes"));
+
+    let small_task = create_task_request(
+        "custom_limit_small",
+        "echo_bytes",
+        vec![any_bytes(b"hello")],
+        vec![],
+        false,
+    );
+    let response = client
+        .submit_task(Request::new(small_task))
+        .await
+        .expect("Failed to submit task");
+    assert_eq!(response.into_inner().result, "hello");
+}
+
+#[tokio::test]
+async fn test_soft_timeout_warning() {
+    let token_file = admin_token_file();
+    let (server, logs) = common::setup_with_logs(&[
+        "--soft-timeout-percent",
+        "50",
+        "--admin-token-file",
+        token_file.path().to_str().expect("Non UTF-8 token path"),
+    ])
+    .await;
+    let mut client = connect_to_server(&server.address()).await;
+    assert_eq!(get_stats(&server.address()).await.timeout_warnings, 0);
+
+    let mut task = create_task_request(
+        "soft_timeout",
+        "sleep_blocking",
+        vec![any_i32(3000)],
+        vec![],
+        false,
+    );
+    task.timeout_seconds = 2;
+
+    let start = std::time::Instant::now();
+    let status = client
+        .submit_task(Request::new(task))
+        .await
+        .expect_err("Expected the task to reach its hard timeout");
+    assert_eq!(status.code(), tonic::Code::DeadlineExceeded);
+    assert!(start.elapsed() >= std::time::Duration::from_secs(2));
+
+    // Give the non-blocking log writer time to flush
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    assert!(
+        logs.contains("Task 'soft_timeout' still running after 1000ms, 50% of its 2s timeout"),
+        "Expected a warning before the hard timeout"
+    );
+    assert_eq!(get_stats(&server.address()).await.timeout_warnings, 1);
+}
@@ -0,0 +1,204 @@
+mod common;
+
+use common::utils::{any_i32, connect_to_server, create_task_request};
+use std::io::Write;
+use task_scheduler::tasks::taskscheduler::task_scheduler_admin_client::TaskSchedulerAdminClient;
+use task_scheduler::tasks::taskscheduler::{ResultRequest, SetAcceptingRequest};
+use tonic::metadata::MetadataValue;
+use tonic::Request;
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_health::pb::HealthCheckRequest;
+use tonic_reflection::pb::v1::server_reflection_client::ServerReflectionClient;
+use tonic_reflection::pb::v1::server_reflection_request::MessageRequest;
+use tonic_reflection::pb::v1::server_reflection_response::MessageResponse;
+use tonic_reflection::pb::v1::ServerReflectionRequest;
+
+#[tokio::test]
+async fn test_health_service_serving() {
+    let server = common::setup().await;
+    let channel = tonic::transport::Channel::from_shared(server.address())
+        .expect("Failed to create shared endpoint")
+        .connect()
+        .await
+        .expect("Failed to create channel");
+    let mut client = HealthClient::new(channel);
+
+    let response = client
+        .check(Request::new(HealthCheckRequest {
+            service: "taskscheduler.TaskScheduler".to_string(),
+        }))
+        .await
+        .expect("Health check failed");
+    assert_eq!(response.into_inner().status, ServingStatus::Serving as i32);
+}
+
+#[tokio::test]
+async fn test_reflection_lists_task_scheduler() {
+    let server = common::setup().await;
+    let channel = tonic::transport::Channel::from_shared(server.address())
+        .expect("Failed to create shared endpoint")
+        .connect()
+        .await
+        .expect("Failed to create channel");
+    let mut client = ServerReflectionClient::new(channel);
+
+    let request = ServerReflectionRequest {
+        host: String::new(),
+        message_request: Some(MessageRequest::ListServices(String::new())),
+    };
+    let mut responses = client
+        .server_reflection_info(Request::new(futures::stream::iter(vec![request])))
+        .await
+        .expect("Reflection request failed")
+        .into_inner();
+
+    let response = responses
+        .message()
+        .await
+        .expect("Failed to read reflection response")
+        .expect("Reflection stream ended without a response");
+
+    let services: Vec<String> = match response.message_response {
+        Some(MessageResponse::ListServicesResponse(list)) => list
+            .service
+            .into_iter()
+            .map(|service| service.name)
+            .collect(),
+        other => panic!("Unexpected reflection response: {:?}", other),
+    };
+    assert!(
+        services.contains(&"taskscheduler.TaskScheduler".to_string()),
+        "Reflection should list the TaskScheduler service, got: {:?}",
+        services
+    );
+}
+
+/// Builds a `SetAccepting` request carrying `token` as its bearer token
+fn set_accepting_request(accepting: bool, token: Option<&str>) -> Request<SetAcceptingRequest> {
+    let mut request = Request::new(SetAcceptingRequest { accepting });
+    if let Some(token) = token {
+        let value: MetadataValue<_> = format!("Bearer {}", token)
+            .parse()
+            .expect("Invalid token metadata");
+        request.metadata_mut().insert("authorization", value);
+    }
+    request
+}
+
+#[tokio::test]
+async fn test_drain_rejects_submissions() {
+    let mut token_file = tempfile::NamedTempFile::new().expect("Failed to create token file");
+    writeln!(token_file, "drain-secret").expect("Failed to write token file");
+    let token_path = token_file.path().to_str().expect("Non UTF-8 token path");
+    let server = common::setup_with_args(&["--admin-token-file", token_path]).await;
+    let mut client = connect_to_server(&server.address()).await;
+    let mut admin_client = TaskSchedulerAdminClient::connect(server.address())
+        .await
+        .expect("Failed to connect admin client");
+    let channel = tonic::transport::Channel::from_shared(server.address())
+        .expect("Failed to create shared endpoint")
+        .connect()
+        .await
+        .expect("Failed to create channel");
+    let mut health_client = HealthClient::new(channel);
+    // Probes check the overall "" service by default, so it must follow the drain too
+    let health_services = ["taskscheduler.TaskScheduler", ""];
+    let health_request = |service: &str| {
+        Request::new(HealthCheckRequest {
+            service: service.to_string(),
+        })
+    };
+
+    let task = create_task_request("drain_before", "add", vec![any_i32(1)], vec![], false);
+    client
+        .submit_task(Request::new(task))
+        .await
+        .expect("Failed to submit task before draining");
+
+    // Draining requires the admin token
+    for token in [None, Some("wrong-secret")] {
+        let status = admin_client
+            .set_accepting(set_accepting_request(false, token))
+            .await
+            .expect_err("Expected an admin call without the token to be rejected");
+        assert_eq!(status.code(), tonic::Code::Unauthenticated);
+    }
+    let task = create_task_request("drain_unauthorized", "add", vec![any_i32(1)], vec![], false);
+    client
+        .submit_task(Request::new(task))
+        .await
+        .expect("Expected a rejected admin call to leave the server accepting");
+
+    let response = admin_client
+        .set_accepting(set_accepting_request(false, Some("drain-secret")))
+        .await
+        .expect("Failed to start draining");
+    assert!(!response.into_inner().accepting);
+
+    // New submissions are rejected and readiness is flipped
+    let task = create_task_request("drain_during", "add", vec![any_i32(1)], vec![], false);
+    let status = client
+        .submit_task(Request::new(task))
+        .await
+        .expect_err("Expected submissions to be rejected while draining");
+    assert_eq!(status.code(), tonic::Code::Unavailable);
+    for service in health_services {
+        let response = health_client
+            .check(health_request(service))
+            .await
+            .expect("Health check failed");
+        assert_eq!(
+            response.into_inner().status,
+            ServingStatus::NotServing as i32,
+            "{:?}",
+            service
+        );
+    }
+
+    // Results can still be queried
+    let result = client
+        .get_result(Request::new(ResultRequest {
+            task_id: "drain_before".to_string(),
+        }))
+        .await
+        .expect("Failed to get result while draining");
+    assert_eq!(result.into_inner().result, "1");
+
+    // Resuming accepts submissions again
+    admin_client
+        .set_accepting(set_accepting_request(true, Some("drain-secret")))
+        .await
+        .expect("Failed to resume accepting");
+    let task = create_task_request("drain_after", "add", vec![any_i32(1)], vec![], false);
+    client
+        .submit_task(Request::new(task))
+        .await
+        .expect("Failed to submit task after resuming");
+    for service in health_services {
+        let response = health_client
+            .check(health_request(service))
+            .await
+            .expect("Health check failed");
+        assert_eq!(
+            response.into_inner().status,
+            ServingStatus::Serving as i32,
+            "{:?}",
+            service
+        );
+    }
+}
+
+#[tokio::test]
+async fn test_admin_service_disabled_without_token() {
+    let server = common::setup().await;
+    let mut admin_client = TaskSchedulerAdminClient::connect(server.address())
+        .await
+        .expect("Failed to connect admin client");
+
+    let status = admin_client
+        .set_accepting(set_accepting_request(false, Some("any-secret")))
+        .await
+        .expect_err("Expected the admin service to be unavailable");
+    assert_eq!(status.code(), tonic::Code::Unimplemented);
+}
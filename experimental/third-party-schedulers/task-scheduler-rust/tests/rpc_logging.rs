@@ -0,0 +1,77 @@
+mod common;
+
+use common::utils::{any_i32, connect_to_server, create_task_request};
+use tonic::Request;
+
+#[tokio::test]
+async fn test_rpc_logging() {
+    let (server, logs) = common::setup_with_logs(&["--rpc-log", "all"]).await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let task = create_task_request("logged_ok", "add", vec![any_i32(1)], vec![], false);
+    let response = client
+        .submit_task(Request::new(task))
+        .await
+        .expect("Failed to submit task");
+    let generated_id = response
+        .metadata()
+        .get("x-request-id")
+        .expect("Expected a generated request id")
+        .to_str()
+        .expect("Non ASCII request id")
+        .to_string();
+
+    // A request id sent by the client ties the RPC log line to the handler's log line
+    let task = create_task_request("logged_error", "no_such_method", vec![], vec![], false);
+    let mut request = Request::new(task);
+    request
+        .metadata_mut()
+        .insert("x-request-id", "client-id-42".parse().unwrap());
+    client
+        .submit_task(request)
+        .await
+        .expect_err("Expected an unknown method to fail");
+
+    // Give the non-blocking log writer time to flush
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    assert!(
+        logs.contains("RPC /taskscheduler.TaskScheduler/SubmitTask returned Ok in"),
+        "Expected the successful call to be logged with its duration"
+    );
+    assert!(
+        logs.contains("RPC /taskscheduler.TaskScheduler/SubmitTask returned NotFound in"),
+        "Expected the failed call to be logged with its status"
+    );
+    assert!(logs.contains(&format!("(request {})", generated_id)));
+    assert!(logs.contains("(request client-id-42)"));
+    assert!(logs.contains("Failed task: logged_error (took"));
+    assert!(logs.contains("request client-id-42). Error:"));
+
+    // Handlers leave per-call logging to the RPC layer
+    assert!(!logs.contains("Received task"));
+    assert!(!logs.contains("Completed task"));
+}
+
+#[tokio::test]
+async fn test_rpc_logging_errors_only() {
+    let (server, logs) = common::setup_with_logs(&[]).await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let task = create_task_request("quiet_ok", "add", vec![any_i32(1)], vec![], false);
+    client
+        .submit_task(Request::new(task))
+        .await
+        .expect("Failed to submit task");
+
+    let task = create_task_request("quiet_error", "add", vec![any_i32(1)], vec![], true);
+    client
+        .submit_task(Request::new(task))
+        .await
+        .expect_err("Expected an unknown async method to fail");
+
+    tokio::time::sleep(std::time::Duration::from_millis(300)).await;
+
+    assert!(!logs.contains("returned Ok"));
+    assert!(logs.contains("RPC /taskscheduler.TaskScheduler/SubmitTask returned NotFound in"));
+}
@@ -0,0 +1,114 @@
+#![cfg(unix)]
+
+mod common;
+
+use common::utils::{any_i32, connect_to_server, create_task_request};
+use std::process::Command;
+use std::time::Duration;
+use tonic::Request;
+use tonic_health::pb::health_check_response::ServingStatus;
+use tonic_health::pb::health_client::HealthClient;
+use tonic_health::pb::HealthCheckRequest;
+
+#[tokio::test]
+async fn test_sigterm_drains_in_flight_tasks() {
+    let mut server = common::setup().await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let in_flight = tokio::spawn(async move {
+        let task = create_task_request(
+            "sigterm_in_flight",
+            "sleep",
+            vec![any_i32(1000)],
+            vec![],
+            true,
+        );
+        client.submit_task(Request::new(task)).await
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    // Probes check the overall "" service by default, so it must stop serving on shutdown
+    let channel = tonic::transport::Channel::from_shared(server.address())
+        .expect("Failed to create shared endpoint")
+        .connect()
+        .await
+        .expect("Failed to create channel");
+    let mut health_updates = HealthClient::new(channel)
+        .watch(Request::new(HealthCheckRequest {
+            service: String::new(),
+        }))
+        .await
+        .expect("Health watch failed")
+        .into_inner();
+    let update = health_updates
+        .message()
+        .await
+        .expect("Failed to read health status")
+        .expect("Health watch ended early");
+    assert_eq!(update.status, ServingStatus::Serving as i32);
+
+    let killed = Command::new("kill")
+        .args(["-TERM", &server.pid().to_string()])
+        .status()
+        .expect("Failed to send SIGTERM");
+    assert!(killed.success());
+
+    let update = health_updates
+        .message()
+        .await
+        .expect("Failed to read health status")
+        .expect("Health watch ended early");
+    assert_eq!(update.status, ServingStatus::NotServing as i32);
+    drop(health_updates);
+
+    // The task started before SIGTERM still completes
+    let response = in_flight
+        .await
+        .expect("In-flight task panicked")
+        .expect("Expected the in-flight task to complete during shutdown");
+    assert_eq!(response.into_inner().result, "Slept 1000 ms");
+
+    let status = server
+        .wait_for_exit(Duration::from_secs(5))
+        .expect("Expected the server to exit after SIGTERM");
+    assert!(status.success(), "Server exited with {}", status);
+}
+
+#[tokio::test]
+async fn test_shutdown_grace_period_expires() {
+    let mut server = common::setup_with_args(&["--shutdown-grace-secs", "1"]).await;
+    let mut client = connect_to_server(&server.address()).await;
+
+    let in_flight = tokio::spawn(async move {
+        let task = create_task_request(
+            "sigterm_outlives_grace",
+            "sleep",
+            vec![any_i32(10_000)],
+            vec![],
+            true,
+        );
+        client.submit_task(Request::new(task)).await
+    });
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let killed = Command::new("kill")
+        .args(["-TERM", &server.pid().to_string()])
+        .status()
+        .expect("Failed to send SIGTERM");
+    assert!(killed.success());
+
+    // The server exits once the grace period expires instead of waiting for the task
+    let status = server
+        .wait_for_exit(Duration::from_secs(5))
+        .expect("Expected the server to exit after the grace period");
+    assert!(
+        !status.success(),
+        "Expected a failing exit status, got {}",
+        status
+    );
+
+    in_flight
+        .await
+        .expect("In-flight task panicked")
+        .expect_err("Expected the in-flight task to be cut off");
+}